@@ -1,35 +1,324 @@
-use std::marker::PhantomData;
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt::{self, Display},
+    marker::PhantomData,
+    panic::Location,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
+use futures::{channel::oneshot, future};
 use leptos::{
     attr::{Attribute, NextAttribute},
     html::ElementType,
     prelude::{
-        DefinedAt, Get, NodeRef, ReadUntracked, RwSignal, Set, Track,
+        DefinedAt, Dispose, Effect, Get, GetUntracked, GetValue, LocalStorage, NodeRef, Notify,
+        ReadUntracked, RwSignal, Set, StoredValue, Track, WithUntracked, Write,
         guards::{Derefable, ReadGuard},
+        on_cleanup, untrack,
     },
     tachys::{html::node_ref::NodeRefContainer, renderer::types::Element},
+    wasm_bindgen::JsCast,
 };
 use send_wrapper::SendWrapper;
 
+/// Type erased storage of an [`AnyNodeRef`], so it can share the signal of a typed [`NodeRef`].
+trait NodeRefStorage: Send + Sync {
+    fn load(&self, el: &Element);
+
+    fn unload(&self, el: &Element);
+
+    fn try_get_untracked(&self) -> Option<Option<Element>>;
+
+    fn track(&self);
+
+    fn notify(&self);
+
+    fn defined_at(&self) -> Option<&'static Location<'static>>;
+}
+
+impl NodeRefStorage for RwSignal<Option<SendWrapper<Element>>> {
+    fn load(&self, el: &Element) {
+        self.set(Some(SendWrapper::new(el.clone())));
+    }
+
+    fn unload(&self, el: &Element) {
+        if self
+            .try_with_untracked(|current| current.as_deref() == Some(el))
+            .unwrap_or_default()
+        {
+            self.try_set(None);
+        }
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<Element>> {
+        Some(self.try_read_untracked()?.as_deref().cloned())
+    }
+
+    fn track(&self) {
+        Track::track(self);
+    }
+
+    fn notify(&self) {
+        Notify::notify(self);
+    }
+
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        DefinedAt::defined_at(self)
+    }
+}
+
+impl<E> NodeRefStorage for NodeRef<E>
+where
+    E: ElementType + Send + Sync + 'static,
+    E::Output: JsCast + Clone + AsRef<Element> + 'static,
+{
+    fn load(&self, el: &Element) {
+        load_node_ref(*self, el);
+    }
+
+    fn unload(&self, el: &Element) {
+        NodeRefUnload::<E>::unload(*self, el);
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<Element>> {
+        Some(
+            self.try_read_untracked()?
+                .as_ref()
+                .map(|el| AsRef::<Element>::as_ref(el).clone()),
+        )
+    }
+
+    fn track(&self) {
+        Track::track(self);
+    }
+
+    fn notify(&self) {
+        Notify::notify(self);
+    }
+
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        DefinedAt::defined_at(self)
+    }
+}
+
+/// Checks that the element has the tag of `E` (unless `E` is a custom element) and is an instance of its native type.
+fn check_element_type<E>(el: &Element) -> Result<(), NodeRefCastError>
+where
+    E: ElementType,
+    E::Output: JsCast,
+{
+    if (!E::TAG.is_empty() && el.local_name() != E::TAG) || !el.has_type::<E::Output>() {
+        Err(NodeRefCastError::new::<E>(el.tag_name()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Loads a typed [`NodeRef`] with the element, if it is of the element type `E`.
+///
+/// [`NodeRefContainer::load`] casts the element without checking, so loading a reference through an [`AnyNodeRef`] or
+/// a [`ComposedNodeRef`](crate::ComposedNodeRef) with an element of another type is skipped instead, with a warning in
+/// debug builds.
+pub(crate) fn load_node_ref<E>(node_ref: NodeRef<E>, el: &Element)
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    match check_element_type::<E>(el) {
+        Ok(()) => NodeRefContainer::<E>::load(node_ref, el),
+        Err(_error) => {
+            #[cfg(debug_assertions)]
+            leptos::logging::warn!("Node reference was not loaded: {_error}.");
+        }
+    }
+}
+
 /// A reactive reference to a DOM node that can be used with the `node_ref` attribute.
+///
+/// With the `ssr` feature, the reference is never loaded: [`Get::get`] returns [`None`], [`AnyNodeRef::on_load`] never
+/// runs its closure and [`AnyNodeRef::loaded`] never resolves. As no element is stored, the reference can be shared
+/// across threads of a multi-threaded server.
 #[derive(Debug)]
-pub struct AnyNodeRef(RwSignal<Option<SendWrapper<Element>>>);
+pub struct AnyNodeRef(StoredValue<Arc<dyn NodeRefStorage>>);
 
 impl AnyNodeRef {
     /// Creates a new [`AnyNodeRef`].
     #[track_caller]
     pub fn new() -> Self {
-        Self(RwSignal::new(None))
+        Self::from_storage(RwSignal::new(None))
+    }
+
+    fn from_storage(storage: impl NodeRefStorage + 'static) -> Self {
+        Self(StoredValue::new(Arc::new(storage)))
+    }
+
+    fn storage(&self) -> Option<Arc<dyn NodeRefStorage>> {
+        // The storage is cloned out of the stored value, so it is not locked while effects run.
+        self.0.try_get_value()
     }
 
     /// Fills the container with the element.
     ///
     /// Type erased version of [`NodeRefContainer::load`].
     pub fn load_any(self, el: &Element) {
-        self.0.set(Some(SendWrapper::new(el.clone())));
+        if cfg!(feature = "ssr") {
+            return;
+        }
+
+        if let Some(storage) = self.storage() {
+            storage.load(el);
+        }
+    }
+
+    /// Empties the container, if it still holds the element.
+    ///
+    /// Type erased version of [`NodeRefUnload::unload`].
+    pub fn unload_any(self, el: &Element) {
+        if let Some(storage) = self.storage() {
+            storage.unload(el);
+        }
+    }
+
+    /// Runs the provided closure once the reference has been loaded with an element.
+    ///
+    /// The closure runs in an effect, so it is not called on the server. The effect is created under the current owner
+    /// and disposed once the closure has run.
+    pub fn on_load<F>(self, f: F)
+    where
+        F: FnOnce(Element) + 'static,
+    {
+        if cfg!(feature = "ssr") {
+            return;
+        }
+
+        let f = Cell::new(Some(f));
+        let effect = Rc::new(Cell::new(None::<Effect<LocalStorage>>));
+
+        effect.set(Some(Effect::new({
+            let effect = Rc::clone(&effect);
+            move |_| {
+                if let Some(el) = self.get()
+                    && let Some(f) = f.take()
+                {
+                    untrack(move || f(el));
+
+                    if let Some(effect) = effect.take() {
+                        effect.dispose();
+                    }
+                }
+            }
+        })));
+    }
+
+    /// Waits until the reference has been loaded with an element and returns it.
+    ///
+    /// The reference is observed under the owner current when this is called, not when the future is first polled, so
+    /// the future can be awaited in `spawn_local`. Resolves immediately if the reference is already loaded. The future
+    /// never resolves if the reference or the owner is disposed before it is loaded.
+    pub fn loaded(self) -> impl Future<Output = Element> {
+        let el = self.get_untracked();
+        let rx = (el.is_none() && !cfg!(feature = "ssr")).then(|| {
+            let (tx, rx) = oneshot::channel();
+            self.on_load(move |el| {
+                _ = tx.send(el);
+            });
+            rx
+        });
+
+        async move {
+            if let Some(el) = el {
+                return el;
+            }
+
+            match rx {
+                Some(rx) => match rx.await {
+                    Ok(el) => el,
+                    Err(_) => future::pending().await,
+                },
+                None => future::pending().await,
+            }
+        }
+    }
+
+    /// Returns the element cast to the native type of the element type `E`, e.g. [`HtmlInputElement`] for
+    /// [`Input`](leptos::html::Input).
+    ///
+    /// Returns [`None`] if the reference is empty or the element is of another type, see [`AnyNodeRef::try_get_as`].
+    ///
+    /// [`HtmlInputElement`]: leptos::web_sys::HtmlInputElement
+    pub fn get_as<E>(&self) -> Option<E::Output>
+    where
+        E: ElementType,
+        E::Output: JsCast,
+    {
+        self.try_get_as::<E>().ok().flatten()
+    }
+
+    /// Returns the element cast to the native type of the element type `E`, or an error if the element is of another
+    /// type.
+    ///
+    /// The element must have the tag of `E` (unless `E` is a custom element) and be an instance of the native type.
+    pub fn try_get_as<E>(&self) -> Result<Option<E::Output>, NodeRefCastError>
+    where
+        E: ElementType,
+        E::Output: JsCast,
+    {
+        self.get()
+            .map(|el| check_element_type::<E>(&el).map(|()| el.unchecked_into::<E::Output>()))
+            .transpose()
+    }
+
+    /// Returns the tag name of the element, or [`None`] if the reference is empty.
+    pub fn tag_name(&self) -> Option<String> {
+        self.get().map(|el| el.tag_name())
+    }
+
+    /// Returns `true` if the reference contains an element of the element type `E`, see [`AnyNodeRef::try_get_as`].
+    pub fn is<E>(&self) -> bool
+    where
+        E: ElementType,
+        E::Output: JsCast,
+    {
+        matches!(self.try_get_as::<E>(), Ok(Some(_)))
     }
 }
 
+/// Error returned by [`AnyNodeRef::try_get_as`] if the element is of another type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NodeRefCastError {
+    /// Tag of the requested element type, empty for custom elements.
+    pub expected: &'static str,
+    /// Tag name of the element in the reference.
+    pub found: String,
+}
+
+impl NodeRefCastError {
+    fn new<E: ElementType>(found: String) -> Self {
+        Self {
+            expected: E::TAG,
+            found,
+        }
+    }
+}
+
+impl Display for NodeRefCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.expected.is_empty() {
+            write!(f, "unexpected element `<{}>`", self.found)
+        } else {
+            write!(
+                f,
+                "expected element `<{}>`, found `<{}>`",
+                self.expected, self.found
+            )
+        }
+    }
+}
+
+impl Error for NodeRefCastError {}
+
 impl Default for AnyNodeRef {
     fn default() -> Self {
         Self::new()
@@ -45,8 +334,8 @@ impl Clone for AnyNodeRef {
 impl Copy for AnyNodeRef {}
 
 impl DefinedAt for AnyNodeRef {
-    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
-        self.0.defined_at()
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.storage()?.defined_at()
     }
 }
 
@@ -61,7 +350,13 @@ where
 
 impl<E: ElementType> NodeRefContainer<E> for AnyNodeRef {
     fn load(self, el: &Element) {
-        self.0.set(Some(SendWrapper::new(el.clone())));
+        self.load_any(el);
+    }
+}
+
+impl<E: ElementType> NodeRefUnload<E> for AnyNodeRef {
+    fn unload(self, el: &Element) {
+        self.unload_any(el);
     }
 }
 
@@ -70,14 +365,24 @@ impl ReadUntracked for AnyNodeRef {
 
     fn try_read_untracked(&self) -> Option<Self::Value> {
         Some(ReadGuard::new(Derefable(
-            self.0.try_read_untracked()?.as_deref().cloned(),
+            self.storage()?.try_get_untracked()?,
         )))
     }
 }
 
 impl Track for AnyNodeRef {
     fn track(&self) {
-        self.0.track();
+        if let Some(storage) = self.storage() {
+            storage.track();
+        }
+    }
+}
+
+impl Notify for AnyNodeRef {
+    fn notify(&self) {
+        if let Some(storage) = self.storage() {
+            storage.notify();
+        }
     }
 }
 
@@ -87,18 +392,14 @@ pub trait IntoAnyNodeRef {
     fn into_any(self) -> AnyNodeRef;
 }
 
+/// The [`AnyNodeRef`] shares the signal of the [`NodeRef`], so both observe the same element whenever either is loaded.
 impl<E> IntoAnyNodeRef for NodeRef<E>
 where
-    E: ElementType,
-    E::Output: AsRef<Element>,
-    NodeRef<E>: Get<Value = Option<E::Output>>,
+    E: ElementType + Send + Sync + 'static,
+    E::Output: JsCast + Clone + AsRef<Element> + 'static,
 {
     fn into_any(self) -> AnyNodeRef {
-        let any_ref = AnyNodeRef::new();
-        if let Some(element) = self.get() {
-            NodeRefContainer::<E>::load(any_ref, element.as_ref());
-        }
-        any_ref
+        AnyNodeRef::from_storage(self)
     }
 }
 
@@ -108,15 +409,64 @@ impl IntoAnyNodeRef for AnyNodeRef {
     }
 }
 
+/// A [`NodeRefContainer`] which can be emptied when its element is unmounted, see
+/// [`AnyNodeRefAttr::unload_on_unmount`].
+pub trait NodeRefUnload<E: ElementType>: NodeRefContainer<E> {
+    /// Empties the container, if it still holds the element.
+    ///
+    /// Does nothing by default.
+    fn unload(self, _el: &Element) {}
+}
+
+impl<E> NodeRefUnload<E> for NodeRef<E>
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + AsRef<Element> + 'static,
+{
+    fn unload(self, el: &Element) {
+        let loaded = self
+            .try_read_untracked()
+            .is_some_and(|current| current.as_ref().map(AsRef::as_ref) == Some(el));
+        if loaded && let Some(mut current) = self.try_write() {
+            *current = None;
+        }
+    }
+}
+
 /// Attribute wrapper for node references that allows conditional rendering across elements.
 ///
 /// Useful when distributing node references across multiple rendering branches.
 #[derive(Debug)]
 pub struct AnyNodeRefAttr<E, C> {
     container: C,
+    unload: Option<fn(C, &Element)>,
     ty: PhantomData<E>,
 }
 
+impl<E, C> AnyNodeRefAttr<E, C>
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    /// Unloads the container when the owner of the element is cleaned up, e.g. when a branch of `Show` is hidden, and
+    /// when it is replaced by another container on rebuild.
+    pub fn unload_on_unmount(self) -> Self
+    where
+        C: NodeRefUnload<E>,
+    {
+        Self {
+            unload: Some(NodeRefUnload::<E>::unload),
+            ..self
+        }
+    }
+
+    fn unload(self, el: &Element) {
+        if let Some(unload) = self.unload {
+            unload(self.container, el);
+        }
+    }
+}
+
 impl<E, C> Clone for AnyNodeRefAttr<E, C>
 where
     C: Clone,
@@ -124,11 +474,36 @@ where
     fn clone(&self) -> Self {
         Self {
             container: self.container.clone(),
+            unload: self.unload,
             ty: PhantomData,
         }
     }
 }
 
+/// Loads the container and registers a cleanup with the current owner, which unloads it when the element is unmounted.
+fn load_container<E, C>(
+    attr: AnyNodeRefAttr<E, C>,
+    el: &Element,
+) -> (Element, Arc<Mutex<AnyNodeRefAttr<E, C>>>)
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    attr.container.clone().load(el);
+
+    let attr = Arc::new(Mutex::new(attr));
+    on_cleanup({
+        let attr = Arc::clone(&attr);
+        let el = SendWrapper::new(el.clone());
+        move || {
+            let attr = attr.lock().expect("lock should not be poisoned").clone();
+            attr.unload(&el);
+        }
+    });
+
+    (el.clone(), attr)
+}
+
 impl<E, C> Attribute for AnyNodeRefAttr<E, C>
 where
     E: ElementType + 'static,
@@ -136,7 +511,7 @@ where
     Element: PartialEq,
 {
     const MIN_LENGTH: usize = 0;
-    type State = Element;
+    type State = (Element, Arc<Mutex<Self>>);
     type AsyncOutput = Self;
     type Cloneable = Self;
     type CloneableOwned = Self;
@@ -156,17 +531,21 @@ where
     }
 
     fn hydrate<const FROM_SERVER: bool>(self, el: &Element) -> Self::State {
-        self.container.load(el);
-        el.clone()
+        load_container(self, el)
     }
 
     fn build(self, el: &Element) -> Self::State {
-        self.container.load(el);
-        el.clone()
+        load_container(self, el)
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        self.container.load(state);
+        let (el, attr) = state;
+        let previous = std::mem::replace(
+            &mut *attr.lock().expect("lock should not be poisoned"),
+            self.clone(),
+        );
+        previous.unload(el);
+        self.container.load(el);
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -199,7 +578,8 @@ where
 
 /// Constructs an attribute to attach an [`AnyNodeRef`] to an element.
 ///
-/// Enables adding node references in conditional/dynamic rendering branches.
+/// Enables adding node references in conditional/dynamic rendering branches. The container stays loaded when the
+/// element is unmounted, unless it is unloaded with [`AnyNodeRefAttr::unload_on_unmount`].
 pub fn any_node_ref<E, C>(container: C) -> AnyNodeRefAttr<E, C>
 where
     E: ElementType,
@@ -207,22 +587,67 @@ where
 {
     AnyNodeRefAttr {
         container,
+        unload: None,
         ty: PhantomData,
     }
 }
 
+/// Element type matching any element, used to attach type erased node references to the root element of a view.
+///
+/// [`AnyNodeRef::get_as`] with this type returns the element itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AnyElement;
+
+impl ElementType for AnyElement {
+    type Output = Element;
+
+    const TAG: &'static str = "";
+    const SELF_CLOSING: bool = false;
+    const ESCAPE_CHILDREN: bool = true;
+    const NAMESPACE: Option<&'static str> = None;
+
+    fn tag(&self) -> &str {
+        Self::TAG
+    }
+}
+
+/// Constructs an attribute to attach a node reference to the root element of a view, without naming its element type.
+///
+/// The attribute can be spread onto components (`<Component {..spread_node_ref(node_ref)} />`), which add it to the
+/// view they render, including type erased and dynamic views. Typed [`NodeRef`]s can be spread after converting them
+/// with [`IntoAnyNodeRef::into_any`]. The container is unloaded when the element is unmounted, see
+/// [`AnyNodeRefAttr::unload_on_unmount`].
+pub fn spread_node_ref<C>(container: C) -> AnyNodeRefAttr<AnyElement, C>
+where
+    C: NodeRefUnload<AnyElement>,
+{
+    any_node_ref(container).unload_on_unmount()
+}
+
 pub mod prelude {
     pub use super::*;
     pub use AnyNodeRef;
     pub use IntoAnyNodeRef;
     pub use any_node_ref;
+    pub use spread_node_ref;
 }
 
 #[cfg(test)]
 mod tests {
-    use leptos::{html, prelude::*};
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use futures::FutureExt;
+    use leptos::{
+        html,
+        prelude::*,
+        tachys::renderer::types::Element,
+        wasm_bindgen::{JsCast, JsValue},
+    };
 
-    use super::{any_node_ref, prelude::*};
+    use super::{NodeRefCastError, NodeRefUnload, any_node_ref, prelude::*};
 
     #[test]
     fn test_any_node_ref_creation() {
@@ -281,6 +706,20 @@ mod tests {
         let _attr = any_node_ref::<html::Div, _>(node_ref);
     }
 
+    #[test]
+    fn test_spread_node_ref() {
+        #[component]
+        fn Child() -> impl IntoView {
+            view! { <div /> }.into_any()
+        }
+
+        let div_ref: NodeRef<html::Div> = NodeRef::new();
+        let _view = view! {
+            <Child {..spread_node_ref(AnyNodeRef::new())} />
+            <Child {..spread_node_ref(div_ref.into_any())} />
+        };
+    }
+
     #[test]
     fn test_defined_at() {
         #[cfg(debug_assertions)]
@@ -298,20 +737,103 @@ mod tests {
         let _untracked = node_ref.try_read_untracked();
     }
 
+    #[test]
+    fn test_unload_empty() {
+        let el: Element = JsValue::NULL.unchecked_into();
+
+        let node_ref = AnyNodeRef::new();
+        node_ref.unload_any(&el);
+        assert!(node_ref.get().is_none());
+
+        let div_ref: NodeRef<html::Div> = NodeRef::new();
+        NodeRefUnload::unload(div_ref, &el);
+        assert!(div_ref.get().is_none());
+    }
+
+    #[test]
+    fn test_on_load_empty() {
+        Owner::new().with(|| {
+            let node_ref = AnyNodeRef::new();
+            let loaded = Arc::new(AtomicUsize::new(0));
+
+            node_ref.on_load({
+                let loaded = Arc::clone(&loaded);
+                move |_| {
+                    loaded.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+
+            assert_eq!(0, loaded.load(Ordering::Relaxed));
+            assert!(node_ref.loaded().now_or_never().is_none());
+        });
+    }
+
+    #[test]
+    fn test_get_as_empty() {
+        let node_ref = AnyNodeRef::new();
+
+        assert_eq!(None, node_ref.get_as::<html::Input>());
+        assert_eq!(Ok(None), node_ref.try_get_as::<html::Input>());
+        assert_eq!(None, node_ref.tag_name());
+        assert!(!node_ref.is::<html::Input>());
+    }
+
+    #[test]
+    fn test_cast_error() {
+        let error = NodeRefCastError::new::<html::Input>("DIV".to_string());
+        assert_eq!("input", error.expected);
+        assert_eq!(
+            "expected element `<input>`, found `<DIV>`",
+            error.to_string()
+        );
+
+        let error = NodeRefCastError::new::<html::Custom<&str>>("svg".to_string());
+        assert_eq!("unexpected element `<svg>`", error.to_string());
+    }
+
     #[test]
     fn test_into_any_identity() {
         let node_ref = AnyNodeRef::new();
         let same_ref = node_ref.into_any();
 
-        // Instead of checking pointer equality, we should verify:
-        // 1. Both refs are initially empty
         assert!(node_ref.get().is_none());
         assert!(same_ref.get().is_none());
+        assert_eq!(node_ref.defined_at(), same_ref.defined_at());
 
-        // 2. When we set one, both should reflect the change
-        // (This would require a mock Element to test properly)
+        // Both refs share the same signal, so notifying one re-runs computations tracking the other.
+        let runs = Arc::new(AtomicUsize::new(0));
+        let tracked = Memo::new({
+            let runs = Arc::clone(&runs);
+            move |_| {
+                same_ref.track();
+                runs.fetch_add(1, Ordering::Relaxed)
+            }
+        });
 
-        // 3. They should have the same defined_at location
-        assert_eq!(node_ref.defined_at(), same_ref.defined_at());
+        assert_eq!(0, tracked.get());
+        node_ref.notify();
+        assert_eq!(1, tracked.get());
+    }
+
+    #[test]
+    fn test_into_any_linked() {
+        let div_ref: NodeRef<html::Div> = NodeRef::new();
+        let any_ref = div_ref.into_any();
+
+        assert!(any_ref.get().is_none());
+        assert_eq!(DefinedAt::defined_at(&div_ref), any_ref.defined_at());
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let tracked = Memo::new({
+            let runs = Arc::clone(&runs);
+            move |_| {
+                any_ref.track();
+                runs.fetch_add(1, Ordering::Relaxed)
+            }
+        });
+
+        assert_eq!(0, tracked.get());
+        Notify::notify(&div_ref);
+        assert_eq!(1, tracked.get());
     }
 }
@@ -0,0 +1,120 @@
+use std::{fmt, sync::Arc};
+
+use leptos::{
+    html::ElementType,
+    tachys::{html::node_ref::NodeRefContainer, renderer::types::Element},
+};
+
+use crate::any_node_ref::NodeRefUnload;
+
+type Callback = Arc<dyn Fn(Element) + Send + Sync>;
+
+/// A node reference which calls functions when its element is mounted and unmounted.
+///
+/// Unmount callbacks are called when attached with [`AnyNodeRefAttr::unload_on_unmount`], not with the `node_ref`
+/// attribute of Leptos.
+///
+/// [`AnyNodeRefAttr::unload_on_unmount`]: crate::AnyNodeRefAttr::unload_on_unmount
+#[derive(Clone, Default)]
+pub struct CallbackRef {
+    on_mount: Option<Callback>,
+    on_unmount: Option<Callback>,
+}
+
+impl CallbackRef {
+    /// Creates a new [`CallbackRef`] without callbacks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the function called with the element when it is mounted.
+    pub fn on_mount<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Element) + Send + Sync + 'static,
+    {
+        self.on_mount = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the function called with the element when it is unmounted.
+    pub fn on_unmount<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Element) + Send + Sync + 'static,
+    {
+        self.on_unmount = Some(Arc::new(callback));
+        self
+    }
+
+    pub(crate) fn mount(&self, el: &Element) {
+        if let Some(on_mount) = &self.on_mount {
+            on_mount(el.clone());
+        }
+    }
+
+    pub(crate) fn unmount(&self, el: &Element) {
+        if let Some(on_unmount) = &self.on_unmount {
+            on_unmount(el.clone());
+        }
+    }
+}
+
+impl fmt::Debug for CallbackRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackRef")
+            .field("on_mount", &self.on_mount.is_some())
+            .field("on_unmount", &self.on_unmount.is_some())
+            .finish()
+    }
+}
+
+impl<E: ElementType> NodeRefContainer<E> for CallbackRef {
+    fn load(self, el: &Element) {
+        self.mount(el);
+    }
+}
+
+impl<E: ElementType> NodeRefUnload<E> for CallbackRef {
+    fn unload(self, el: &Element) {
+        self.unmount(el);
+    }
+}
+
+/// Creates a [`CallbackRef`] which calls the function with the element when it is mounted.
+pub fn on_mount<F>(callback: F) -> CallbackRef
+where
+    F: Fn(Element) + Send + Sync + 'static,
+{
+    CallbackRef::new().on_mount(callback)
+}
+
+/// Creates a [`CallbackRef`] which calls the function with the element when it is unmounted.
+pub fn on_unmount<F>(callback: F) -> CallbackRef
+where
+    F: Fn(Element) + Send + Sync + 'static,
+{
+    CallbackRef::new().on_unmount(callback)
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::html;
+
+    use super::*;
+    use crate::any_node_ref::any_node_ref;
+
+    #[test]
+    fn test_callback_ref() {
+        let callback_ref = on_mount(|_| {}).on_unmount(|_| {});
+        assert!(callback_ref.on_mount.is_some());
+        assert!(callback_ref.on_unmount.is_some());
+
+        let callback_ref = on_unmount(|_| {});
+        assert!(callback_ref.on_mount.is_none());
+        assert_eq!(
+            "CallbackRef { on_mount: false, on_unmount: true }",
+            format!("{callback_ref:?}")
+        );
+
+        let _attr = any_node_ref::<html::Div, _>(callback_ref);
+    }
+}
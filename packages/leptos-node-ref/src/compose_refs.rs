@@ -0,0 +1,169 @@
+use std::{fmt, sync::Arc};
+
+use leptos::{
+    html::ElementType,
+    prelude::NodeRef,
+    tachys::{html::node_ref::NodeRefContainer, renderer::types::Element},
+    wasm_bindgen::JsCast,
+};
+
+use crate::{
+    any_node_ref::{AnyNodeRef, NodeRefUnload, load_node_ref},
+    callback_ref::CallbackRef,
+};
+
+type LoadFn = Arc<dyn Fn(&Element) + Send + Sync>;
+
+/// A node reference which can be composed with others into a [`ComposedNodeRef`].
+///
+/// Created from a typed [`NodeRef`], an [`AnyNodeRef`], a [`CallbackRef`] or another [`ComposedNodeRef`].
+#[derive(Clone)]
+pub struct ComposableRef {
+    load: LoadFn,
+    unload: LoadFn,
+}
+
+impl ComposableRef {
+    fn new<L, U>(load: L, unload: U) -> Self
+    where
+        L: Fn(&Element) + Send + Sync + 'static,
+        U: Fn(&Element) + Send + Sync + 'static,
+    {
+        Self {
+            load: Arc::new(load),
+            unload: Arc::new(unload),
+        }
+    }
+}
+
+impl fmt::Debug for ComposableRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ComposableRef").finish_non_exhaustive()
+    }
+}
+
+impl From<AnyNodeRef> for ComposableRef {
+    fn from(value: AnyNodeRef) -> Self {
+        Self::new(move |el| value.load_any(el), move |el| value.unload_any(el))
+    }
+}
+
+impl<E> From<NodeRef<E>> for ComposableRef
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+    NodeRef<E>: NodeRefUnload<E>,
+{
+    fn from(value: NodeRef<E>) -> Self {
+        Self::new(
+            move |el| load_node_ref(value, el),
+            move |el| NodeRefUnload::<E>::unload(value, el),
+        )
+    }
+}
+
+impl From<CallbackRef> for ComposableRef {
+    fn from(value: CallbackRef) -> Self {
+        let unload_value = value.clone();
+        Self::new(
+            move |el| value.mount(el),
+            move |el| unload_value.unmount(el),
+        )
+    }
+}
+
+impl From<ComposedNodeRef> for ComposableRef {
+    fn from(value: ComposedNodeRef) -> Self {
+        let unload_value = value.clone();
+        Self::new(
+            move |el| value.load_all(el),
+            move |el| unload_value.unload_all(el),
+        )
+    }
+}
+
+/// A node reference which loads the element into each of its inner refs.
+///
+/// Useful when a component needs its own reference to an element while also forwarding the reference of its consumer.
+#[derive(Clone, Debug, Default)]
+pub struct ComposedNodeRef(Arc<[ComposableRef]>);
+
+impl<R: Into<ComposableRef>> FromIterator<R> for ComposedNodeRef {
+    fn from_iter<T: IntoIterator<Item = R>>(iter: T) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl ComposedNodeRef {
+    fn load_all(&self, el: &Element) {
+        for node_ref in self.0.iter() {
+            (node_ref.load)(el);
+        }
+    }
+
+    fn unload_all(&self, el: &Element) {
+        for node_ref in self.0.iter() {
+            (node_ref.unload)(el);
+        }
+    }
+}
+
+impl<E: ElementType> NodeRefContainer<E> for ComposedNodeRef {
+    fn load(self, el: &Element) {
+        self.load_all(el);
+    }
+}
+
+impl<E: ElementType> NodeRefUnload<E> for ComposedNodeRef {
+    fn unload(self, el: &Element) {
+        self.unload_all(el);
+    }
+}
+
+/// Composes multiple node references into one, which loads the element into each of them when it is mounted.
+///
+/// Refs of different types can be combined by converting them into a [`ComposableRef`] first.
+pub fn compose_refs<I, R>(refs: I) -> ComposedNodeRef
+where
+    I: IntoIterator<Item = R>,
+    R: Into<ComposableRef>,
+{
+    refs.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::{html, prelude::*};
+
+    use super::*;
+    use crate::{any_node_ref::any_node_ref, callback_ref::on_mount};
+
+    #[test]
+    fn test_compose_refs() {
+        let a = AnyNodeRef::new();
+        let b = AnyNodeRef::new();
+        let _attr = any_node_ref::<html::Div, _>(compose_refs([a, b]));
+
+        assert!(a.get().is_none());
+        assert!(b.get().is_none());
+    }
+
+    #[test]
+    fn test_compose_mixed_refs() {
+        let div_ref: NodeRef<html::Div> = NodeRef::new();
+        let any_ref = AnyNodeRef::new();
+        let inner_ref = AnyNodeRef::new();
+
+        let composed = compose_refs::<_, ComposableRef>([
+            div_ref.into(),
+            any_ref.into(),
+            compose_refs([inner_ref]).into(),
+            on_mount(|_| {}).into(),
+        ]);
+        let _attr = any_node_ref::<html::Div, _>(composed);
+
+        assert!(div_ref.get().is_none());
+        assert!(any_ref.get().is_none());
+        assert!(inner_ref.get().is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use leptos::{
+    ev,
+    prelude::{
+        ArcRwSignal, Callable, Callback, Effect, GetUntracked, RwSignal, Set, Signal, Update, With,
+        WithUntracked, on_cleanup, window_event_listener,
+    },
+    wasm_bindgen::JsCast,
+};
+use web_sys::Node;
+
+use crate::any_node_ref::AnyNodeRef;
+
+/// Reason for dismissing a [`DismissableLayer`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DismissReason {
+    EscapeKeyDown,
+    PointerDownOutside,
+    FocusOutside,
+}
+
+/// Options for [`use_dismissable_layer`].
+#[derive(Clone, Copy, Debug)]
+pub struct DismissableLayerOptions {
+    /// Whether pressing the escape key dismisses the layer, if it is the topmost layer.
+    pub escape_key_down: bool,
+    /// Whether a pointer down outside of the layer dismisses it.
+    pub pointer_down_outside: bool,
+    /// Whether focus moving outside of the layer dismisses it.
+    pub focus_outside: bool,
+    /// Called when the layer is dismissed.
+    pub on_dismiss: Option<Callback<DismissReason>>,
+}
+
+impl Default for DismissableLayerOptions {
+    fn default() -> Self {
+        Self {
+            escape_key_down: true,
+            pointer_down_outside: true,
+            focus_outside: true,
+            on_dismiss: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Layer {
+    id: usize,
+    refs: Vec<AnyNodeRef>,
+}
+
+thread_local! {
+    static LAYERS: ArcRwSignal<Vec<Layer>> = ArcRwSignal::new(vec![]);
+}
+
+fn layers() -> ArcRwSignal<Vec<Layer>> {
+    LAYERS.with(ArcRwSignal::clone)
+}
+
+/// Returns `true` if the node is inside the layer or a layer stacked above it.
+///
+/// Layers opened from within a layer (e.g. a nested menu rendered in a portal) are stacked above it, so interacting
+/// with them does not dismiss the layers below.
+fn is_inside(layers: &[Layer], id: usize, node: &Node) -> bool {
+    layers
+        .iter()
+        .skip_while(|layer| layer.id != id)
+        .flat_map(|layer| &layer.refs)
+        .filter_map(|node_ref| node_ref.get_untracked())
+        .any(|el| AsRef::<Node>::as_ref(&el).contains(Some(node)))
+}
+
+/// A layer which can be dismissed by interactions outside of it, see [`use_dismissable_layer`].
+#[derive(Clone, Copy, Debug)]
+pub struct DismissableLayer {
+    id: usize,
+    dismissed: RwSignal<Option<DismissReason>>,
+    on_dismiss: Option<Callback<DismissReason>>,
+}
+
+impl DismissableLayer {
+    /// Returns `true` if the layer is the most recently opened layer which has not been closed.
+    pub fn is_topmost(&self) -> Signal<bool> {
+        let id = self.id;
+        let layers = layers();
+        Signal::derive(move || {
+            layers.with(|layers| layers.last().is_some_and(|layer| layer.id == id))
+        })
+    }
+
+    /// Returns the reason of the latest dismissal, or [`None`] if the layer has not been dismissed.
+    pub fn dismissed(&self) -> Signal<Option<DismissReason>> {
+        self.dismissed.into()
+    }
+
+    /// Dismisses the layer, e.g. from a close button.
+    pub fn dismiss(&self, reason: DismissReason) {
+        self.dismissed.set(Some(reason));
+        if let Some(on_dismiss) = self.on_dismiss {
+            on_dismiss.try_run(reason);
+        }
+    }
+}
+
+/// Opens a layer which is dismissed by pressing the escape key, a pointer down outside of it or focus moving outside
+/// of it.
+///
+/// The layer consists of the elements of all node references, e.g. the content and the trigger of a popover. It is
+/// stacked above the layers opened before it, until the current owner is cleaned up. Only the topmost layer is
+/// dismissed by the escape key, and interactions within layers stacked above a layer are inside of it.
+///
+/// With the `ssr` feature, the layer is not opened.
+pub fn use_dismissable_layer<I>(refs: I, options: DismissableLayerOptions) -> DismissableLayer
+where
+    I: IntoIterator<Item = AnyNodeRef>,
+{
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let layer = DismissableLayer {
+        id,
+        dismissed: RwSignal::new(None),
+        on_dismiss: options.on_dismiss,
+    };
+
+    if cfg!(feature = "ssr") {
+        return layer;
+    }
+
+    let refs = refs.into_iter().collect();
+    layers().update(|layers| layers.push(Layer { id, refs }));
+
+    let handles = Arc::new(Mutex::new(vec![]));
+
+    Effect::new({
+        let handles = Arc::clone(&handles);
+        move |_| {
+            let is_outside = move |target: Option<web_sys::EventTarget>| {
+                target
+                    .and_then(|target| target.dyn_into::<Node>().ok())
+                    .is_some_and(|node| {
+                        !layers().with_untracked(|layers| is_inside(layers, id, &node))
+                    })
+            };
+
+            let mut handles = handles.lock().expect("lock should not be poisoned");
+
+            if options.escape_key_down {
+                handles.push(window_event_listener(ev::keydown, move |event| {
+                    let is_topmost = layers()
+                        .with_untracked(|layers| layers.last().is_some_and(|layer| layer.id == id));
+                    if event.key() == "Escape" && is_topmost {
+                        layer.dismiss(DismissReason::EscapeKeyDown);
+                    }
+                }));
+            }
+
+            if options.pointer_down_outside {
+                handles.push(window_event_listener(ev::pointerdown, move |event| {
+                    if is_outside(event.target()) {
+                        layer.dismiss(DismissReason::PointerDownOutside);
+                    }
+                }));
+            }
+
+            if options.focus_outside {
+                handles.push(window_event_listener(ev::focusin, move |event| {
+                    if is_outside(event.target()) {
+                        layer.dismiss(DismissReason::FocusOutside);
+                    }
+                }));
+            }
+        }
+    });
+
+    on_cleanup(move || {
+        for handle in handles
+            .lock()
+            .expect("lock should not be poisoned")
+            .drain(..)
+        {
+            handle.remove();
+        }
+        layers().update(|layers| layers.retain(|layer| layer.id != id));
+    });
+
+    layer
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::{Get, Owner};
+
+    use super::*;
+
+    #[cfg(not(feature = "ssr"))]
+    #[test]
+    fn test_stacking() {
+        let owner = Owner::new();
+        let (outer, inner_owner, inner) = owner.with(|| {
+            let outer =
+                use_dismissable_layer([AnyNodeRef::new()], DismissableLayerOptions::default());
+            assert!(outer.is_topmost().get());
+
+            let inner_owner = Owner::new();
+            let inner = inner_owner.with(|| {
+                use_dismissable_layer(
+                    [AnyNodeRef::new(), AnyNodeRef::new()],
+                    DismissableLayerOptions::default(),
+                )
+            });
+
+            (outer, inner_owner, inner)
+        });
+
+        let outer_topmost = owner.with(|| outer.is_topmost());
+        assert!(!outer_topmost.get());
+        assert!(inner_owner.with(|| inner.is_topmost()).get());
+
+        inner_owner.cleanup();
+        assert!(outer_topmost.get());
+        assert_eq!(None, outer.dismissed().get());
+    }
+
+    #[test]
+    fn test_dismiss() {
+        Owner::new().with(|| {
+            let reasons = RwSignal::new(vec![]);
+            let options = DismissableLayerOptions {
+                on_dismiss: Some(Callback::new(move |reason| {
+                    reasons.update(|reasons| reasons.push(reason));
+                })),
+                ..Default::default()
+            };
+            let layer = use_dismissable_layer([AnyNodeRef::new()], options);
+
+            layer.dismiss(DismissReason::EscapeKeyDown);
+            layer.dismiss(DismissReason::FocusOutside);
+
+            assert_eq!(Some(DismissReason::FocusOutside), layer.dismissed().get());
+            assert_eq!(
+                vec![DismissReason::EscapeKeyDown, DismissReason::FocusOutside],
+                reasons.get()
+            );
+        });
+    }
+}
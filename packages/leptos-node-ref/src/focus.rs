@@ -0,0 +1,355 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use leptos::{
+    ev,
+    prelude::{GetUntracked, document, set_timeout, window, window_event_listener},
+    tachys::renderer::types::Element,
+    wasm_bindgen::JsCast,
+};
+use web_sys::{HtmlElement, Node};
+
+use crate::{any_node_ref::AnyNodeRef, observer::observe_element};
+
+/// Candidates for tabbable elements, which are filtered by [`is_tabbable`].
+const TABBABLE_SELECTOR: &str = "a[href], area[href], audio[controls], button, details > summary:first-of-type, \
+    embed, iframe, input:not([type=\"hidden\"]), object, select, textarea, video[controls], \
+    [contenteditable]:not([contenteditable=\"false\"]), [tabindex]";
+
+/// Returns `true` if the element or one of its ancestors up to the container is not rendered.
+fn is_hidden(el: &Element, container: &Element) -> bool {
+    let window = window();
+    let property = |el: &Element, name: &str| {
+        window
+            .get_computed_style(el)
+            .ok()
+            .flatten()
+            .and_then(|style| style.get_property_value(name).ok())
+            .unwrap_or_default()
+    };
+
+    if property(el, "visibility") == "hidden" {
+        return true;
+    }
+
+    let mut current = Some(el.clone());
+    while let Some(el) = current {
+        if &el == container {
+            break;
+        }
+        if property(&el, "display") == "none" {
+            return true;
+        }
+        current = el.parent_element();
+    }
+
+    false
+}
+
+fn is_tabbable(el: &HtmlElement, container: &Element) -> bool {
+    el.tab_index() >= 0 && !el.matches(":disabled").unwrap_or(false) && !is_hidden(el, container)
+}
+
+/// Returns the tabbable descendants of the container, in document order.
+///
+/// Elements are tabbable if they are focusable by keyboard, i.e. they are enabled, rendered and do not have a negative
+/// `tabindex`. Positive `tabindex` values do not change the order.
+pub fn tabbable_elements(container: &Element) -> Vec<HtmlElement> {
+    let Ok(nodes) = container.query_selector_all(TABBABLE_SELECTOR) else {
+        return vec![];
+    };
+
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .filter(|el| is_tabbable(el, container))
+        .collect()
+}
+
+/// Focuses the element, returning `true` if it received focus.
+fn focus(el: &HtmlElement) -> bool {
+    _ = el.focus();
+    document().active_element().as_ref() == Some(el.as_ref())
+}
+
+/// Focuses the first of the candidates which can receive focus, returning `true` if one did.
+pub fn focus_first<I>(candidates: I) -> bool
+where
+    I: IntoIterator<Item = HtmlElement>,
+{
+    candidates.into_iter().any(|candidate| focus(&candidate))
+}
+
+/// Options for [`use_focus_scope`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FocusScopeOptions {
+    /// Whether tabbing from the last tabbable element focuses the first one and vice versa.
+    pub looped: bool,
+    /// Whether focus is kept within the scope, so it can not be moved outside by keyboard, pointer or script.
+    pub trapped: bool,
+}
+
+/// A scope managing the focus within the element of a node reference, see [`use_focus_scope`].
+#[derive(Clone, Copy, Debug)]
+pub struct FocusScope {
+    node_ref: AnyNodeRef,
+}
+
+impl FocusScope {
+    /// Focuses the first tabbable element in the scope, returning `true` if one received focus.
+    pub fn focus_first(&self) -> bool {
+        self.node_ref
+            .get_untracked()
+            .is_some_and(|container| focus_first(tabbable_elements(&container)))
+    }
+
+    /// Focuses the last tabbable element in the scope, returning `true` if one received focus.
+    pub fn focus_last(&self) -> bool {
+        self.node_ref
+            .get_untracked()
+            .is_some_and(|container| focus_first(tabbable_elements(&container).into_iter().rev()))
+    }
+}
+
+thread_local! {
+    static SCOPES: RefCell<Vec<usize>> = const { RefCell::new(vec![]) };
+}
+
+/// Stacks a scope above the scopes mounted before it, returning its id.
+fn push_scope() -> usize {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    SCOPES.with_borrow_mut(|scopes| scopes.push(id));
+    id
+}
+
+fn remove_scope(id: usize) {
+    SCOPES.with_borrow_mut(|scopes| scopes.retain(|scope| *scope != id));
+}
+
+/// Returns `true` if the scope is the most recently mounted scope which has not been unmounted.
+fn is_topmost_scope(id: usize) -> bool {
+    SCOPES.with_borrow(|scopes| scopes.last() == Some(&id))
+}
+
+/// Manages the focus within the element of the node reference while it is loaded.
+///
+/// When the element is loaded, the first tabbable element is focused, or the element itself if it has none (which
+/// requires a `tabindex`, e.g. `-1`). When the element is unloaded or the current owner is cleaned up, focus is
+/// restored to the element that was focused before.
+///
+/// Scopes are stacked in the order their elements are loaded. Only the topmost scope traps and loops the focus, so a
+/// scope loaded within a trapped scope (e.g. a nested dialog) can receive focus, and the outer scope is active again
+/// once it is unloaded.
+pub fn use_focus_scope(node_ref: AnyNodeRef, options: FocusScopeOptions) -> FocusScope {
+    observe_element(node_ref, move |container| {
+        let id = push_scope();
+        let previously_focused = document().active_element();
+        let container_node: &Node = container.as_ref();
+
+        if !container_node.contains(previously_focused.as_ref().map(AsRef::as_ref))
+            && !focus_first(tabbable_elements(container))
+            && let Some(container) = container.dyn_ref::<HtmlElement>()
+        {
+            _ = container.focus();
+        }
+
+        let last_focused = Rc::new(RefCell::new(
+            document()
+                .active_element()
+                .filter(|el| container_node.contains(Some(el)))
+                .and_then(|el| el.dyn_into::<HtmlElement>().ok()),
+        ));
+
+        // Moves the focus back into the container, if it is still the topmost trapped scope.
+        let refocus = Rc::new({
+            let container = container.clone();
+            let last_focused = Rc::clone(&last_focused);
+            move || {
+                if !options.trapped || !is_topmost_scope(id) {
+                    return;
+                }
+
+                let last_focused = last_focused.borrow().clone();
+                if !last_focused.is_some_and(|el| el.is_connected() && focus(&el))
+                    && !focus_first(tabbable_elements(&container))
+                    && let Some(container) = container.dyn_ref::<HtmlElement>()
+                {
+                    _ = container.focus();
+                }
+            }
+        });
+
+        let focus_in = window_event_listener(ev::focusin, {
+            let container = container.clone();
+            let last_focused = Rc::clone(&last_focused);
+            let refocus = Rc::clone(&refocus);
+            move |event| {
+                let target = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<HtmlElement>().ok());
+                let container_node: &Node = container.as_ref();
+
+                match target {
+                    Some(target) if container_node.contains(Some(&target)) => {
+                        *last_focused.borrow_mut() = Some(target);
+                    }
+                    _ => refocus(),
+                }
+            }
+        });
+
+        let focus_out = options.trapped.then(|| {
+            let container = container.clone();
+            window_event_listener(ev::focusout, move |event| {
+                let container_node: &Node = container.as_ref();
+
+                match event
+                    .related_target()
+                    .and_then(|target| target.dyn_into::<Node>().ok())
+                {
+                    Some(related_target) => {
+                        if !container_node.contains(Some(&related_target)) {
+                            refocus();
+                        }
+                    }
+                    // Focus moves to the body without a `focusin` event, e.g. when clicking a non-focusable area or
+                    // removing the focused element. It also has no related target when the window loses focus, in
+                    // which case the focused element does not change, so only refocus once focus has moved.
+                    None => {
+                        let refocus = Rc::clone(&refocus);
+                        set_timeout(
+                            move || {
+                                let document = document();
+                                let is_body_focused = document.active_element().is_none_or(|el| {
+                                    document
+                                        .body()
+                                        .is_some_and(|body| AsRef::<Element>::as_ref(&body) == &el)
+                                });
+                                if is_body_focused {
+                                    refocus();
+                                }
+                            },
+                            Duration::ZERO,
+                        );
+                    }
+                }
+            })
+        });
+
+        let key_down = (options.looped || options.trapped).then(|| {
+            let container = container.clone();
+            window_event_listener(ev::keydown, move |event| {
+                if event.key() != "Tab"
+                    || event.alt_key()
+                    || event.ctrl_key()
+                    || event.meta_key()
+                    || !is_topmost_scope(id)
+                {
+                    return;
+                }
+
+                let focused = document().active_element();
+                let container_node: &Node = container.as_ref();
+                if !container_node.contains(focused.as_ref().map(AsRef::as_ref)) {
+                    return;
+                }
+
+                let tabbables = tabbable_elements(&container);
+                let (Some(first), Some(last)) = (tabbables.first(), tabbables.last()) else {
+                    if focused.as_ref() == Some(&container) {
+                        event.prevent_default();
+                    }
+                    return;
+                };
+
+                let edge = if event.shift_key() {
+                    (focused.as_ref() == Some(first.as_ref())).then_some(last)
+                } else {
+                    (focused.as_ref() == Some(last.as_ref())).then_some(first)
+                };
+                if let Some(next) = edge {
+                    event.prevent_default();
+                    if options.looped {
+                        focus(next);
+                    }
+                }
+            })
+        });
+
+        Box::new(move || {
+            remove_scope(id);
+            focus_in.remove();
+            if let Some(focus_out) = focus_out {
+                focus_out.remove();
+            }
+            if let Some(key_down) = key_down {
+                key_down.remove();
+            }
+
+            let previously_focused = previously_focused
+                .filter(|el| el.is_connected())
+                .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+                .or_else(|| document().body());
+            if let Some(el) = previously_focused {
+                _ = el.focus();
+            }
+        })
+    });
+
+    FocusScope { node_ref }
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::Owner;
+
+    use super::*;
+
+    #[test]
+    fn test_focus_first_empty() {
+        assert!(!focus_first(Vec::<HtmlElement>::new()));
+    }
+
+    #[test]
+    fn test_scope_stack() {
+        let outer = push_scope();
+        assert!(is_topmost_scope(outer));
+
+        let inner = push_scope();
+        let sibling = push_scope();
+        assert!(!is_topmost_scope(outer));
+        assert!(is_topmost_scope(sibling));
+
+        // Scopes can be unloaded out of order.
+        remove_scope(inner);
+        assert!(is_topmost_scope(sibling));
+
+        remove_scope(sibling);
+        assert!(is_topmost_scope(outer));
+
+        remove_scope(outer);
+        assert!(!is_topmost_scope(outer));
+    }
+
+    #[test]
+    fn test_unloaded_focus_scope() {
+        Owner::new().with(|| {
+            let scope = use_focus_scope(
+                AnyNodeRef::new(),
+                FocusScopeOptions {
+                    looped: true,
+                    trapped: true,
+                },
+            );
+
+            assert!(!scope.focus_first());
+            assert!(!scope.focus_last());
+        });
+    }
+}
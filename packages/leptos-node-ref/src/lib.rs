@@ -1,5 +1,15 @@
 //! Node reference extras for [Leptos](https://leptos.dev/).
 
 mod any_node_ref;
+mod callback_ref;
+mod compose_refs;
+mod dismissable_layer;
+mod focus;
+mod observer;
 
 pub use any_node_ref::*;
+pub use callback_ref::*;
+pub use compose_refs::*;
+pub use dismissable_layer::*;
+pub use focus::*;
+pub use observer::*;
@@ -0,0 +1,448 @@
+use std::sync::{Arc, Mutex};
+
+use leptos::{
+    prelude::{Effect, Get, RwSignal, Set, Signal, on_cleanup},
+    tachys::renderer::types::Element,
+    wasm_bindgen::{JsCast, JsValue, closure::Closure},
+};
+use send_wrapper::SendWrapper;
+use web_sys::{
+    IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit, MutationObserver,
+    MutationObserverInit, MutationRecord, ResizeObserver, ResizeObserverEntry, ResizeObserverSize,
+    js_sys::Array,
+};
+
+use crate::any_node_ref::AnyNodeRef;
+
+pub(crate) type Disconnect = Box<dyn FnOnce()>;
+
+type DisconnectSlot = Mutex<Option<SendWrapper<Disconnect>>>;
+
+fn take_disconnect(slot: &DisconnectSlot) -> Option<Disconnect> {
+    slot.lock()
+        .expect("lock should not be poisoned")
+        .take()
+        .map(SendWrapper::take)
+}
+
+/// Observes the element of the node reference whenever it is loaded.
+///
+/// The observer is disconnected when the element is unloaded or replaced, and when the current owner is cleaned up.
+pub(crate) fn observe_element<F>(node_ref: AnyNodeRef, observe: F)
+where
+    F: Fn(&Element) -> Disconnect + 'static,
+{
+    if cfg!(feature = "ssr") {
+        return;
+    }
+
+    let slot = Arc::new(DisconnectSlot::default());
+
+    Effect::new({
+        let slot = Arc::clone(&slot);
+        move |_| {
+            let el = node_ref.get();
+
+            if let Some(disconnect) = take_disconnect(&slot) {
+                disconnect();
+            }
+
+            if let Some(el) = el {
+                let disconnect = observe(&el);
+                *slot.lock().expect("lock should not be poisoned") =
+                    Some(SendWrapper::new(disconnect));
+            }
+        }
+    });
+
+    on_cleanup(move || {
+        if let Some(disconnect) = take_disconnect(&slot) {
+            disconnect();
+        }
+    });
+}
+
+/// Size of an element's box, in CSS pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Sizes of an element reported by a [`ResizeObserver`].
+///
+/// Widths and heights are the inline and block sizes, so they are swapped in vertical writing modes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResizeEntry {
+    pub border_box: ElementSize,
+    pub content_box: ElementSize,
+}
+
+impl From<&ResizeObserverEntry> for ResizeEntry {
+    fn from(entry: &ResizeObserverEntry) -> Self {
+        let rect = entry.content_rect();
+        let content_rect = ElementSize {
+            width: rect.width(),
+            height: rect.height(),
+        };
+
+        Self {
+            border_box: box_size(entry.border_box_size(), content_rect),
+            content_box: box_size(entry.content_box_size(), content_rect),
+        }
+    }
+}
+
+/// Reads the first box size, falling back to the content rectangle in browsers without box sizes.
+fn box_size(sizes: Array, fallback: ElementSize) -> ElementSize {
+    if !Array::is_array(&sizes) || sizes.length() == 0 {
+        return fallback;
+    }
+
+    let size = sizes.get(0).unchecked_into::<ResizeObserverSize>();
+    ElementSize {
+        width: size.inline_size(),
+        height: size.block_size(),
+    }
+}
+
+/// Observes the size of the element in the node reference with a [`ResizeObserver`].
+///
+/// The signal is [`None`] until the element has been observed and after it is unloaded.
+pub fn use_resize_observer(node_ref: AnyNodeRef) -> Signal<Option<ResizeEntry>> {
+    let entry = RwSignal::new(None);
+
+    observe_element(node_ref, move |el| {
+        let callback = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+            if let Some(last) = entries.iter().last() {
+                entry.try_set(Some(ResizeEntry::from(
+                    &last.unchecked_into::<ResizeObserverEntry>(),
+                )));
+            }
+        });
+
+        let observer = match ResizeObserver::new(callback.as_ref().unchecked_ref()) {
+            Ok(observer) => observer,
+            Err(error) => {
+                leptos::logging::warn!("Failed to create ResizeObserver: {error:?}");
+                return Box::new(|| {});
+            }
+        };
+        observer.observe(el);
+
+        Box::new(move || {
+            observer.disconnect();
+            drop(callback);
+            entry.try_set(None);
+        })
+    });
+
+    entry.into()
+}
+
+/// Observes the border box size of the element in the node reference, see [`use_resize_observer`].
+pub fn use_element_size(node_ref: AnyNodeRef) -> Signal<Option<ElementSize>> {
+    let entry = use_resize_observer(node_ref);
+
+    Signal::derive(move || entry.get().map(|entry| entry.border_box))
+}
+
+/// Options for [`use_intersection`].
+#[derive(Clone, Debug, Default)]
+pub struct IntersectionOptions {
+    /// Element used as viewport, the document viewport if [`None`]. Observation starts once the root is loaded.
+    pub root: Option<AnyNodeRef>,
+    /// Margin around the root, e.g. `10px 20px`.
+    pub root_margin: Option<String>,
+    /// Ratios of visibility at which the intersection is updated, `[0.0]` if empty.
+    pub threshold: Vec<f64>,
+}
+
+/// Intersection of an element with the root, reported by an [`IntersectionObserver`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Intersection {
+    pub is_intersecting: bool,
+    pub intersection_ratio: f64,
+}
+
+impl From<&IntersectionObserverEntry> for Intersection {
+    fn from(entry: &IntersectionObserverEntry) -> Self {
+        Self {
+            is_intersecting: entry.is_intersecting(),
+            intersection_ratio: entry.intersection_ratio(),
+        }
+    }
+}
+
+/// Observes the intersection of the element in the node reference with an [`IntersectionObserver`].
+///
+/// The signal is [`None`] until the element has been observed and after it is unloaded.
+pub fn use_intersection(
+    node_ref: AnyNodeRef,
+    options: IntersectionOptions,
+) -> Signal<Option<Intersection>> {
+    let intersection = RwSignal::new(None);
+
+    observe_element(node_ref, move |el| {
+        let init = IntersectionObserverInit::new();
+        if let Some(root) = options.root {
+            match root.get() {
+                Some(root) => init.set_root(Some(&root)),
+                None => return Box::new(|| {}),
+            }
+        }
+        if let Some(root_margin) = &options.root_margin {
+            init.set_root_margin(root_margin);
+        }
+        if !options.threshold.is_empty() {
+            init.set_threshold(
+                &options
+                    .threshold
+                    .iter()
+                    .map(|threshold| JsValue::from_f64(*threshold))
+                    .collect::<Array>(),
+            );
+        }
+
+        let callback = Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+            if let Some(last) = entries.iter().last() {
+                intersection.try_set(Some(Intersection::from(
+                    &last.unchecked_into::<IntersectionObserverEntry>(),
+                )));
+            }
+        });
+
+        let observer = match IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &init,
+        ) {
+            Ok(observer) => observer,
+            Err(error) => {
+                leptos::logging::warn!("Failed to create IntersectionObserver: {error:?}");
+                return Box::new(|| {});
+            }
+        };
+        observer.observe(el);
+
+        Box::new(move || {
+            observer.disconnect();
+            drop(callback);
+            intersection.try_set(None);
+        })
+    });
+
+    intersection.into()
+}
+
+/// Options for [`use_mutation_observer`], observing changes to the children by default.
+///
+/// At least one kind of mutation must be observed. The old value options and the attribute filter enable observing
+/// attributes and character data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MutationObserverOptions {
+    pub child_list: bool,
+    pub attributes: bool,
+    pub character_data: bool,
+    pub subtree: bool,
+    pub attribute_old_value: bool,
+    pub character_data_old_value: bool,
+    /// Names of the observed attributes, all attributes if [`None`].
+    pub attribute_filter: Option<Vec<String>>,
+}
+
+impl Default for MutationObserverOptions {
+    fn default() -> Self {
+        Self {
+            child_list: true,
+            attributes: false,
+            character_data: false,
+            subtree: false,
+            attribute_old_value: false,
+            character_data_old_value: false,
+            attribute_filter: None,
+        }
+    }
+}
+
+impl MutationObserverOptions {
+    /// Returns `true` if the options observe at least one kind of mutation, as required by the `MutationObserver`.
+    pub fn is_valid(&self) -> bool {
+        self.child_list
+            || self.attributes
+            || self.character_data
+            || self.attribute_old_value
+            || self.character_data_old_value
+            || self.attribute_filter.is_some()
+    }
+}
+
+impl From<&MutationObserverOptions> for MutationObserverInit {
+    fn from(options: &MutationObserverOptions) -> Self {
+        let init = MutationObserverInit::new();
+        init.set_child_list(options.child_list);
+        // Omitted unless enabled, so they are implied by the old value options and the attribute filter.
+        if options.attributes {
+            init.set_attributes(true);
+        }
+        if options.character_data {
+            init.set_character_data(true);
+        }
+        init.set_subtree(options.subtree);
+        if options.attribute_old_value {
+            init.set_attribute_old_value(true);
+        }
+        if options.character_data_old_value {
+            init.set_character_data_old_value(true);
+        }
+        if let Some(attribute_filter) = &options.attribute_filter {
+            init.set_attribute_filter(
+                &attribute_filter
+                    .iter()
+                    .map(|name| JsValue::from_str(name))
+                    .collect::<Array>(),
+            );
+        }
+        init
+    }
+}
+
+/// Kind of a [`Mutation`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MutationKind {
+    ChildList,
+    Attributes,
+    CharacterData,
+}
+
+impl MutationKind {
+    fn from_type(value: &str) -> Option<Self> {
+        match value {
+            "childList" => Some(Self::ChildList),
+            "attributes" => Some(Self::Attributes),
+            "characterData" => Some(Self::CharacterData),
+            _ => None,
+        }
+    }
+}
+
+/// Mutation of an element or its subtree, reported by a [`MutationObserver`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mutation {
+    pub kind: MutationKind,
+    pub attribute_name: Option<String>,
+    /// Previous value, if enabled in the [`MutationObserverOptions`].
+    pub old_value: Option<String>,
+}
+
+/// Observes mutations of the element in the node reference with a [`MutationObserver`].
+///
+/// The signal holds the mutations of the latest batch reported by the observer, and is emptied when the element is
+/// unloaded. If the options are not [valid](MutationObserverOptions::is_valid), a warning is logged and the element is
+/// not observed.
+pub fn use_mutation_observer(
+    node_ref: AnyNodeRef,
+    options: MutationObserverOptions,
+) -> Signal<Vec<Mutation>> {
+    let mutations = RwSignal::new(vec![]);
+
+    observe_element(node_ref, move |el| {
+        let callback = Closure::<dyn Fn(Array)>::new(move |records: Array| {
+            mutations.try_set(
+                records
+                    .iter()
+                    .map(|record| record.unchecked_into::<MutationRecord>())
+                    .filter_map(|record| {
+                        Some(Mutation {
+                            kind: MutationKind::from_type(&record.type_())?,
+                            attribute_name: record.attribute_name(),
+                            old_value: record.old_value(),
+                        })
+                    })
+                    .collect(),
+            );
+        });
+
+        let observer =
+            match MutationObserver::new(callback.as_ref().unchecked_ref()).and_then(|observer| {
+                observer.observe_with_options(el, &MutationObserverInit::from(&options))?;
+                Ok(observer)
+            }) {
+                Ok(observer) => observer,
+                Err(error) => {
+                    leptos::logging::warn!("Failed to observe mutations: {error:?}");
+                    return Box::new(|| {});
+                }
+            };
+
+        Box::new(move || {
+            observer.disconnect();
+            drop(callback);
+            mutations.try_set(vec![]);
+        })
+    });
+
+    mutations.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::{GetUntracked, Owner};
+
+    use super::*;
+
+    #[test]
+    fn test_mutation_kind() {
+        assert_eq!(
+            Some(MutationKind::ChildList),
+            MutationKind::from_type("childList")
+        );
+        assert_eq!(
+            Some(MutationKind::Attributes),
+            MutationKind::from_type("attributes")
+        );
+        assert_eq!(
+            Some(MutationKind::CharacterData),
+            MutationKind::from_type("characterData")
+        );
+        assert_eq!(None, MutationKind::from_type("subtree"));
+    }
+
+    #[test]
+    fn test_mutation_observer_options() {
+        assert!(MutationObserverOptions::default().is_valid());
+        assert!(
+            MutationObserverOptions {
+                child_list: false,
+                attribute_filter: Some(vec!["class".to_string()]),
+                ..Default::default()
+            }
+            .is_valid()
+        );
+        assert!(
+            !MutationObserverOptions {
+                child_list: false,
+                subtree: true,
+                ..Default::default()
+            }
+            .is_valid()
+        );
+    }
+
+    #[test]
+    fn test_unloaded() {
+        Owner::new().with(|| {
+            let node_ref = AnyNodeRef::new();
+
+            assert_eq!(None, use_resize_observer(node_ref).get_untracked());
+            assert_eq!(None, use_element_size(node_ref).get_untracked());
+            assert_eq!(
+                None,
+                use_intersection(node_ref, IntersectionOptions::default()).get_untracked()
+            );
+            assert_eq!(
+                Vec::<Mutation>::new(),
+                use_mutation_observer(node_ref, MutationObserverOptions::default()).get_untracked()
+            );
+        });
+    }
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "ssr")]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use futures::FutureExt;
+use leptos::{html, prelude::*};
+use leptos_node_ref::{
+    AnyNodeRef, ComposableRef, IntoAnyNodeRef, any_node_ref, compose_refs, on_mount,
+    spread_node_ref,
+};
+
+#[component]
+fn Child() -> impl IntoView {
+    view! { <span>"Child"</span> }.into_any()
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let node_ref = AnyNodeRef::new();
+        let div_ref: NodeRef<html::Div> = NodeRef::new();
+        let mounted = Arc::new(AtomicUsize::new(0));
+
+        let callback_ref = on_mount({
+            let mounted = Arc::clone(&mounted);
+            move |_| {
+                mounted.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        let html = view! {
+            <div {..any_node_ref::<html::Div, _>(node_ref)}>
+                <Child {..spread_node_ref(compose_refs::<_, ComposableRef>([div_ref.into(), callback_ref.into()]))} />
+            </div>
+        }
+        .to_html();
+
+        assert_eq!("<div><span>Child</span></div>", html);
+        assert!(node_ref.get().is_none());
+        assert!(div_ref.get().is_none());
+        assert!(div_ref.into_any().get().is_none());
+        assert_eq!(0, mounted.load(Ordering::Relaxed));
+    });
+}
+
+#[test]
+fn test_on_load() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let node_ref = AnyNodeRef::new();
+        let loaded = Arc::new(AtomicUsize::new(0));
+
+        node_ref.on_load({
+            let loaded = Arc::clone(&loaded);
+            move |_| {
+                loaded.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        _ = view! { <div {..any_node_ref::<html::Div, _>(node_ref)} /> }.to_html();
+
+        assert_eq!(0, loaded.load(Ordering::Relaxed));
+        assert!(node_ref.loaded().now_or_never().is_none());
+    });
+}
+
+#[test]
+fn test_multi_threaded() {
+    let owner = Owner::new();
+    let node_ref = owner.with(AnyNodeRef::new);
+
+    let threads = (0..4)
+        .map(|_| {
+            let owner = owner.clone();
+            thread::spawn(move || {
+                owner.with(|| {
+                    let html = view! {
+                        <div {..any_node_ref::<html::Div, _>(node_ref)}>
+                            <Child {..spread_node_ref(node_ref)} />
+                        </div>
+                    }
+                    .to_html();
+
+                    assert_eq!("<div><span>Child</span></div>", html);
+                    assert!(node_ref.get().is_none());
+                    assert!(node_ref.tag_name().is_none());
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().expect("thread should not panic");
+    }
+
+    // Disposing the reference does not drop an element on another thread.
+    drop(owner);
+    assert!(node_ref.try_get().is_none());
+}
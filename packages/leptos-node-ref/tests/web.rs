@@ -0,0 +1,736 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use any_spawner::Executor;
+use futures::channel::oneshot;
+use leptos::{
+    attr::Attribute,
+    html,
+    prelude::*,
+    tachys::{html::node_ref::NodeRefContainer, renderer::types::Element},
+    wasm_bindgen::JsCast,
+};
+use leptos_node_ref::{
+    AnyNodeRef, ComposableRef, DismissReason, DismissableLayerOptions, ElementSize,
+    FocusScopeOptions, IntersectionOptions, IntoAnyNodeRef, Mutation, MutationKind,
+    MutationObserverOptions, NodeRefUnload, any_node_ref, compose_refs, on_mount, on_unmount,
+    spread_node_ref, use_dismissable_layer, use_element_size, use_focus_scope, use_intersection,
+    use_mutation_observer,
+};
+use send_wrapper::SendWrapper;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{
+    FocusEvent, FocusEventInit, HtmlElement, KeyboardEvent, KeyboardEventInit, PointerEvent,
+    PointerEventInit,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Elements = Arc<Mutex<Vec<SendWrapper<Element>>>>;
+
+fn init_executor() {
+    _ = Executor::init_wasm_bindgen();
+}
+
+async fn sleep(duration: Duration) {
+    let (tx, rx) = oneshot::channel();
+    set_timeout(
+        move || {
+            _ = tx.send(());
+        },
+        duration,
+    );
+    _ = rx.await;
+}
+
+/// Waits for observers and effects until the condition holds, for at most a second.
+async fn wait_until(condition: impl Fn() -> bool) -> bool {
+    for _ in 0..100 {
+        if condition() {
+            return true;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    condition()
+}
+
+fn append_to_body(tag: &str, style: &str) -> Element {
+    let el = create_element(tag);
+    el.set_attribute("style", style)
+        .expect("attribute should be set");
+    document()
+        .body()
+        .expect("body should exist")
+        .append_child(&el)
+        .expect("element should be appended");
+    el
+}
+
+fn append_child(parent: &Element, tag: &str) -> HtmlElement {
+    let el = create_element(tag);
+    parent
+        .append_child(&el)
+        .expect("element should be appended");
+    el.unchecked_into()
+}
+
+fn is_focused(el: &HtmlElement) -> bool {
+    document().active_element().as_ref() == Some(el.as_ref())
+}
+
+fn dispatch_focus_event(target: &Element, event_type: &str, related_target: Option<&Element>) {
+    let init = FocusEventInit::new();
+    init.set_bubbles(true);
+    init.set_related_target(related_target.map(AsRef::as_ref));
+    let event = FocusEvent::new_with_focus_event_init_dict(event_type, &init)
+        .expect("event should be created");
+    target
+        .dispatch_event(&event)
+        .expect("event should be dispatched");
+}
+
+fn dispatch_key_down(target: &Element, key: &str, shift_key: bool) {
+    let init = KeyboardEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_key(key);
+    init.set_shift_key(shift_key);
+    let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+        .expect("event should be created");
+    target
+        .dispatch_event(&event)
+        .expect("event should be dispatched");
+}
+
+fn dispatch_pointer_down(target: &Element) {
+    let init = PointerEventInit::new();
+    init.set_bubbles(true);
+    let event = PointerEvent::new_with_event_init_dict("pointerdown", &init)
+        .expect("event should be created");
+    target
+        .dispatch_event(&event)
+        .expect("event should be dispatched");
+}
+
+fn create_element(tag: &str) -> Element {
+    document()
+        .create_element(tag)
+        .expect("element should be created")
+}
+
+fn elements(elements: &Elements) -> Vec<Element> {
+    elements
+        .lock()
+        .expect("lock should not be poisoned")
+        .iter()
+        .map(|el| Element::clone(el))
+        .collect()
+}
+
+fn push_element(elements: &Elements) -> impl Fn(Element) + Send + Sync + 'static {
+    let elements = Arc::clone(elements);
+    move |el| {
+        elements
+            .lock()
+            .expect("lock should not be poisoned")
+            .push(SendWrapper::new(el));
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_compose_refs() {
+    let div_ref: NodeRef<html::Div> = NodeRef::new();
+    let any_ref = AnyNodeRef::new();
+    let inner_ref = AnyNodeRef::new();
+    let mounted = Elements::default();
+    let unmounted = Elements::default();
+    let callback_ref = on_mount(push_element(&mounted)).on_unmount(push_element(&unmounted));
+
+    let composed = compose_refs::<_, ComposableRef>([
+        div_ref.into(),
+        any_ref.into(),
+        callback_ref.into(),
+        compose_refs([inner_ref]).into(),
+    ]);
+    let el = create_element("div");
+
+    NodeRefContainer::<html::Div>::load(composed.clone(), &el);
+    assert_eq!(Some(el.clone()), div_ref.get_untracked().map(Element::from));
+    assert_eq!(Some(el.clone()), any_ref.get_untracked());
+    assert_eq!(Some(el.clone()), inner_ref.get_untracked());
+    assert_eq!(vec![el.clone()], elements(&mounted));
+    assert!(elements(&unmounted).is_empty());
+
+    NodeRefUnload::<html::Div>::unload(composed, &el);
+    assert_eq!(None, div_ref.get_untracked());
+    assert_eq!(None, any_ref.get_untracked());
+    assert_eq!(None, inner_ref.get_untracked());
+    assert_eq!(vec![el.clone()], elements(&mounted));
+    assert_eq!(vec![el], elements(&unmounted));
+}
+
+#[wasm_bindgen_test]
+fn test_unload_other_element() {
+    let any_ref = AnyNodeRef::new();
+    let composed = compose_refs([any_ref]);
+    let el = create_element("div");
+
+    NodeRefContainer::<html::Div>::load(composed.clone(), &el);
+    NodeRefUnload::<html::Div>::unload(composed, &create_element("div"));
+    assert_eq!(Some(el), any_ref.get_untracked());
+}
+
+#[wasm_bindgen_test]
+fn test_load_typed_node_ref() {
+    let input_ref: NodeRef<html::Input> = NodeRef::new();
+    let any_ref = input_ref.into_any();
+
+    any_ref.load_any(&create_element("div"));
+    assert_eq!(None, input_ref.get_untracked());
+    assert_eq!(None, any_ref.get_untracked());
+
+    let composed = compose_refs([input_ref]);
+    NodeRefContainer::<html::Div>::load(composed.clone(), &create_element("div"));
+    assert_eq!(None, input_ref.get_untracked());
+
+    let input = create_element("input");
+    NodeRefContainer::<html::Div>::load(composed, &input);
+    assert_eq!(Some(input.clone()), any_ref.get_untracked());
+    assert!(any_ref.is::<html::Input>());
+}
+
+#[wasm_bindgen_test]
+fn test_unload_on_cleanup() {
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let div_ref: NodeRef<html::Div> = NodeRef::new();
+    let kept_ref = AnyNodeRef::new();
+    let unmounted = Elements::default();
+
+    let _state = owner.with(|| {
+        view! {
+            <div {..any_node_ref::<html::Div, _>(node_ref).unload_on_unmount()}>
+                <div {..any_node_ref::<html::Div, _>(div_ref).unload_on_unmount()} />
+                <span {..any_node_ref::<html::Span, _>(on_unmount(push_element(&unmounted))).unload_on_unmount()} />
+                <p {..any_node_ref::<html::P, _>(kept_ref)} />
+            </div>
+        }
+        .build()
+    });
+
+    assert!(node_ref.get_untracked().is_some());
+    assert!(div_ref.get_untracked().is_some());
+    assert!(kept_ref.get_untracked().is_some());
+    assert!(elements(&unmounted).is_empty());
+
+    owner.cleanup();
+    assert_eq!(None, node_ref.get_untracked());
+    assert_eq!(None, div_ref.get_untracked());
+    assert!(kept_ref.get_untracked().is_some());
+    assert_eq!(
+        vec![String::from("SPAN")],
+        elements(&unmounted)
+            .iter()
+            .map(Element::tag_name)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_unload_on_rebuild() {
+    let a = AnyNodeRef::new();
+    let b = AnyNodeRef::new();
+    let el = create_element("div");
+
+    let mut state = any_node_ref::<html::Div, _>(a)
+        .unload_on_unmount()
+        .build(&el);
+    assert_eq!(Some(el.clone()), a.get_untracked());
+
+    any_node_ref::<html::Div, _>(b)
+        .unload_on_unmount()
+        .rebuild(&mut state);
+    assert_eq!(None, a.get_untracked());
+    assert_eq!(Some(el.clone()), b.get_untracked());
+
+    // Rebuilding with the same reference keeps it loaded.
+    any_node_ref::<html::Div, _>(b)
+        .unload_on_unmount()
+        .rebuild(&mut state);
+    assert_eq!(Some(el.clone()), b.get_untracked());
+
+    // Without unloading on unmount, the replaced reference stays loaded.
+    any_node_ref::<html::Div, _>(a).rebuild(&mut state);
+    any_node_ref::<html::Div, _>(b).rebuild(&mut state);
+    assert_eq!(Some(el.clone()), a.get_untracked());
+    assert_eq!(Some(el), b.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn test_on_load() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let loaded = Elements::default();
+
+    // The future observes the reference under the owner current when it is created, not when it is polled.
+    let future = owner.with(|| {
+        node_ref.on_load(push_element(&loaded));
+        node_ref.loaded()
+    });
+
+    let el = create_element("div");
+    node_ref.load_any(&el);
+    assert_eq!(el, future.await);
+
+    node_ref.load_any(&create_element("span"));
+    Executor::tick().await;
+    assert_eq!(vec![el], elements(&loaded));
+}
+
+#[wasm_bindgen_test]
+async fn test_loaded_disposed() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+
+    let future = owner.with(|| node_ref.loaded());
+    owner.cleanup();
+    node_ref.load_any(&create_element("div"));
+    Executor::tick().await;
+
+    assert!(futures::FutureExt::now_or_never(future).is_none());
+}
+
+#[component]
+fn Child() -> impl IntoView {
+    view! {
+        <section>
+            <span />
+        </section>
+    }
+    .into_any()
+}
+
+#[wasm_bindgen_test]
+fn test_spread_node_ref() {
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let section_ref: NodeRef<html::Section> = NodeRef::new();
+
+    let _state = owner.with(|| {
+        view! {
+            <Child {..spread_node_ref(node_ref)} />
+            <Child {..spread_node_ref(section_ref.into_any())} />
+        }
+        .build()
+    });
+
+    assert_eq!(
+        Some(String::from("SECTION")),
+        node_ref.get_untracked().map(|el| el.tag_name())
+    );
+    assert!(section_ref.get_untracked().is_some());
+    assert_ne!(
+        node_ref.get_untracked(),
+        section_ref.get_untracked().map(Element::from)
+    );
+
+    owner.cleanup();
+    assert_eq!(None, node_ref.get_untracked());
+    assert_eq!(None, section_ref.get_untracked());
+}
+
+#[wasm_bindgen_test]
+async fn test_mutation_observer() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let mutations = owner.with(|| {
+        use_mutation_observer(
+            node_ref,
+            MutationObserverOptions {
+                attribute_filter: Some(vec![String::from("title")]),
+                ..Default::default()
+            },
+        )
+    });
+
+    let el = create_element("div");
+    node_ref.load_any(&el);
+    Executor::tick().await;
+
+    el.append_child(&create_element("span"))
+        .expect("child should be appended");
+    assert!(wait_until(|| !mutations.get_untracked().is_empty()).await);
+    assert_eq!(
+        vec![Mutation {
+            kind: MutationKind::ChildList,
+            attribute_name: None,
+            old_value: None,
+        }],
+        mutations.get_untracked()
+    );
+
+    el.set_attribute("title", "Title")
+        .expect("attribute should be set");
+    assert!(
+        wait_until(|| mutations.get_untracked()
+            == vec![Mutation {
+                kind: MutationKind::Attributes,
+                attribute_name: Some(String::from("title")),
+                old_value: None,
+            }])
+        .await
+    );
+
+    node_ref.unload_any(&el);
+    assert!(wait_until(|| mutations.get_untracked().is_empty()).await);
+
+    owner.cleanup();
+}
+
+#[wasm_bindgen_test]
+async fn test_element_size() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let size = owner.with(|| use_element_size(node_ref));
+
+    let el = append_to_body("div", "width: 100px; height: 50px;");
+    node_ref.load_any(&el);
+    assert!(
+        wait_until(|| size.get_untracked()
+            == Some(ElementSize {
+                width: 100.0,
+                height: 50.0,
+            }))
+        .await
+    );
+
+    el.set_attribute("style", "width: 200px; height: 50px;")
+        .expect("attribute should be set");
+    assert!(
+        wait_until(|| size.get_untracked()
+            == Some(ElementSize {
+                width: 200.0,
+                height: 50.0,
+            }))
+        .await
+    );
+
+    node_ref.unload_any(&el);
+    assert!(wait_until(|| size.get_untracked().is_none()).await);
+
+    owner.cleanup();
+    el.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_intersection() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let intersection = owner.with(|| use_intersection(node_ref, IntersectionOptions::default()));
+    let is_intersecting = move || {
+        intersection
+            .get_untracked()
+            .map(|intersection| intersection.is_intersecting)
+    };
+
+    let el = append_to_body(
+        "div",
+        "position: fixed; top: 0; left: 0; width: 10px; height: 10px;",
+    );
+    node_ref.load_any(&el);
+    assert!(wait_until(|| is_intersecting() == Some(true)).await);
+
+    el.set_attribute(
+        "style",
+        "position: fixed; top: -100px; left: 0; width: 10px; height: 10px;",
+    )
+    .expect("attribute should be set");
+    assert!(wait_until(|| is_intersecting() == Some(false)).await);
+
+    el.set_attribute(
+        "style",
+        "position: fixed; top: 0; left: 0; width: 10px; height: 10px;",
+    )
+    .expect("attribute should be set");
+    assert!(wait_until(|| is_intersecting() == Some(true)).await);
+
+    node_ref.unload_any(&el);
+    assert!(wait_until(|| is_intersecting().is_none()).await);
+
+    owner.cleanup();
+    el.remove();
+}
+
+const TRAPPED: FocusScopeOptions = FocusScopeOptions {
+    looped: false,
+    trapped: true,
+};
+
+#[wasm_bindgen_test]
+async fn test_focus_scope_looped() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    owner.with(|| {
+        use_focus_scope(
+            node_ref,
+            FocusScopeOptions {
+                looped: true,
+                trapped: false,
+            },
+        )
+    });
+
+    let container = append_to_body("div", "");
+    let first = append_child(&container, "button");
+    let middle = append_child(&container, "button");
+    let last = append_child(&container, "button");
+    node_ref.load_any(&container);
+    assert!(wait_until(|| is_focused(&first)).await);
+
+    dispatch_key_down(&first, "Tab", true);
+    assert!(is_focused(&last));
+
+    dispatch_key_down(&last, "Tab", false);
+    assert!(is_focused(&first));
+
+    // Tabbing away from other elements is left to the browser.
+    _ = middle.focus();
+    dispatch_key_down(&middle, "Tab", false);
+    assert!(is_focused(&middle));
+
+    owner.cleanup();
+    container.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_focus_scope_trapped() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    owner.with(|| use_focus_scope(node_ref, TRAPPED));
+
+    let outside = append_to_body("button", "").unchecked_into::<HtmlElement>();
+    let container = append_to_body("div", "");
+    let first = append_child(&container, "button");
+    let second = append_child(&container, "button");
+    node_ref.load_any(&container);
+    assert!(wait_until(|| is_focused(&first)).await);
+
+    _ = second.focus();
+    assert!(is_focused(&second));
+
+    // Focus moved outside by script is moved back to the last focused element.
+    _ = outside.focus();
+    dispatch_focus_event(&outside, "focusin", None);
+    assert!(wait_until(|| is_focused(&second)).await);
+
+    // Focus lost to the body without a `focusin` event is moved back once it has moved.
+    second.remove();
+    dispatch_focus_event(&container, "focusout", None);
+    assert!(wait_until(|| is_focused(&first)).await);
+
+    // Focus moving to an element outside is moved back.
+    _ = first.blur();
+    dispatch_focus_event(&first, "focusout", Some(&outside));
+    assert!(wait_until(|| is_focused(&first)).await);
+
+    owner.cleanup();
+    container.remove();
+    outside.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_focus_scope_nested() {
+    init_executor();
+    let outer_owner = Owner::new();
+    let inner_owner = Owner::new();
+    let outer_ref = AnyNodeRef::new();
+    let inner_ref = AnyNodeRef::new();
+    outer_owner.with(|| use_focus_scope(outer_ref, TRAPPED));
+    inner_owner.with(|| use_focus_scope(inner_ref, TRAPPED));
+
+    let outside = append_to_body("button", "").unchecked_into::<HtmlElement>();
+    let outer = append_to_body("div", "");
+    let outer_button = append_child(&outer, "button");
+    let inner = append_to_body("div", "");
+    let inner_button = append_child(&inner, "button");
+
+    outer_ref.load_any(&outer);
+    assert!(wait_until(|| is_focused(&outer_button)).await);
+
+    inner_ref.load_any(&inner);
+    assert!(wait_until(|| is_focused(&inner_button)).await);
+
+    // The inner scope takes over the trap, even from the outer scope.
+    _ = outer_button.focus();
+    dispatch_focus_event(&outer_button, "focusin", None);
+    assert!(wait_until(|| is_focused(&inner_button)).await);
+
+    // Unloading the inner scope restores the focus and hands the trap back to the outer scope.
+    inner_owner.cleanup();
+    assert!(is_focused(&outer_button));
+
+    _ = outside.focus();
+    dispatch_focus_event(&outside, "focusin", None);
+    assert!(wait_until(|| is_focused(&outer_button)).await);
+
+    outer_owner.cleanup();
+    outer.remove();
+    inner.remove();
+    outside.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_focus_scope_restore() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    owner.with(|| use_focus_scope(node_ref, FocusScopeOptions::default()));
+
+    let previous = append_to_body("button", "").unchecked_into::<HtmlElement>();
+    let container = append_to_body("div", "");
+    let button = append_child(&container, "button");
+
+    _ = previous.focus();
+    assert!(is_focused(&previous));
+
+    node_ref.load_any(&container);
+    assert!(wait_until(|| is_focused(&button)).await);
+
+    owner.cleanup();
+    assert!(is_focused(&previous));
+
+    container.remove();
+    previous.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_dismissable_layer() {
+    init_executor();
+    let outer_owner = Owner::new();
+    let inner_owner = Owner::new();
+    let outer_ref = AnyNodeRef::new();
+    let inner_ref = AnyNodeRef::new();
+    let outer_layer =
+        outer_owner.with(|| use_dismissable_layer([outer_ref], DismissableLayerOptions::default()));
+    // The inner layer is a child of the outer layer rendered elsewhere, e.g. a nested menu in a portal.
+    let inner_layer =
+        inner_owner.with(|| use_dismissable_layer([inner_ref], DismissableLayerOptions::default()));
+
+    let outside = append_to_body("button", "");
+    let outer = append_to_body("div", "");
+    let inner = append_to_body("div", "");
+    let inner_button = append_child(&inner, "button");
+    outer_ref.load_any(&outer);
+    inner_ref.load_any(&inner);
+    Executor::tick().await;
+
+    // Interacting with the child layer does not dismiss the parent.
+    dispatch_pointer_down(&inner_button);
+    dispatch_focus_event(&inner_button, "focusin", None);
+    assert_eq!(None, outer_layer.dismissed().get_untracked());
+    assert_eq!(None, inner_layer.dismissed().get_untracked());
+
+    // Interacting with the parent layer dismisses the child.
+    dispatch_pointer_down(&outer);
+    assert_eq!(None, outer_layer.dismissed().get_untracked());
+    assert_eq!(
+        Some(DismissReason::PointerDownOutside),
+        inner_layer.dismissed().get_untracked()
+    );
+
+    // Only the topmost layer is dismissed by the escape key.
+    dispatch_key_down(&outside, "Escape", false);
+    assert_eq!(None, outer_layer.dismissed().get_untracked());
+    assert_eq!(
+        Some(DismissReason::EscapeKeyDown),
+        inner_layer.dismissed().get_untracked()
+    );
+
+    inner_owner.cleanup();
+    dispatch_key_down(&outside, "Escape", false);
+    assert_eq!(
+        Some(DismissReason::EscapeKeyDown),
+        outer_layer.dismissed().get_untracked()
+    );
+
+    outer_owner.cleanup();
+    outside.remove();
+    outer.remove();
+    inner.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_dismissable_layer_outside() {
+    init_executor();
+    let owner = Owner::new();
+    let node_ref = AnyNodeRef::new();
+    let reasons = Arc::new(Mutex::new(vec![]));
+    let layer = owner.with(|| {
+        use_dismissable_layer(
+            [node_ref],
+            DismissableLayerOptions {
+                on_dismiss: Some(Callback::new({
+                    let reasons = Arc::clone(&reasons);
+                    move |reason| {
+                        reasons
+                            .lock()
+                            .expect("lock should not be poisoned")
+                            .push(reason);
+                    }
+                })),
+                ..Default::default()
+            },
+        )
+    });
+
+    let outside = append_to_body("button", "");
+    let container = append_to_body("div", "");
+    let button = append_child(&container, "button");
+    node_ref.load_any(&container);
+    Executor::tick().await;
+
+    dispatch_pointer_down(&button);
+    dispatch_focus_event(&button, "focusin", None);
+    assert_eq!(None, layer.dismissed().get_untracked());
+
+    dispatch_pointer_down(&outside);
+    assert_eq!(
+        Some(DismissReason::PointerDownOutside),
+        layer.dismissed().get_untracked()
+    );
+
+    dispatch_focus_event(&outside, "focusin", None);
+    assert_eq!(
+        Some(DismissReason::FocusOutside),
+        layer.dismissed().get_untracked()
+    );
+    assert_eq!(
+        vec![
+            DismissReason::PointerDownOutside,
+            DismissReason::FocusOutside
+        ],
+        *reasons.lock().expect("lock should not be poisoned")
+    );
+
+    // The layer is closed once the owner is cleaned up.
+    owner.cleanup();
+    dispatch_pointer_down(&outside);
+    assert_eq!(
+        2,
+        reasons.lock().expect("lock should not be poisoned").len()
+    );
+
+    outside.remove();
+    container.remove();
+}
@@ -0,0 +1,385 @@
+use std::fmt::{self, Display};
+
+use indexmap::{IndexMap, IndexSet};
+use leptos::{
+    attr::IntoAttributeValue,
+    prelude::{Get, RenderEffect, Signal, With},
+    tachys::{
+        html::class::IntoClass,
+        renderer::{Rndr, types::ClassList},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ClassCondition {
+    Static(bool),
+    Signal(Signal<bool>),
+}
+
+impl ClassCondition {
+    fn get(&self) -> bool {
+        match self {
+            ClassCondition::Static(enabled) => *enabled,
+            ClassCondition::Signal(enabled) => enabled.get(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ClassPart {
+    Token(String, ClassCondition),
+    Signal(Signal<String>),
+}
+
+fn token_parts(tokens: &str, condition: ClassCondition) -> impl Iterator<Item = ClassPart> {
+    tokens
+        .split_whitespace()
+        .map(move |token| ClassPart::Token(token.to_string(), condition))
+}
+
+/// A list of class tokens, which can be enabled by conditions and signals.
+///
+/// Tokens are de-duplicated when resolved. The last part mentioning a token decides whether it is enabled, while the
+/// token keeps the position of its first occurrence.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Class {
+    parts: Vec<ClassPart>,
+    #[cfg(feature = "tailwind")]
+    tailwind: bool,
+}
+
+impl Class {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn from_parts(parts: impl IntoIterator<Item = ClassPart>) -> Self {
+        Self {
+            parts: parts.into_iter().collect(),
+            #[cfg(feature = "tailwind")]
+            tailwind: false,
+        }
+    }
+
+    /// Merges the class with defaults. Tokens of the class take precedence over the defaults, so a token enabled in the
+    /// defaults can be disabled by the class.
+    pub fn with_defaults<I: Into<Self>>(self, defaults: I) -> Self {
+        let defaults = defaults.into();
+        let mut parts = defaults.parts;
+        parts.extend(self.parts);
+
+        Self {
+            parts,
+            #[cfg(feature = "tailwind")]
+            tailwind: self.tailwind || defaults.tailwind,
+        }
+    }
+
+    /// Merges the class with defaults like [`Class::with_defaults`], additionally resolving conflicting Tailwind CSS
+    /// classes, so `p-4` overrides `p-2` from the defaults.
+    ///
+    /// Conflicts are resolved with [`tailwind_merge`](crate::tailwind_merge) whenever the tokens are resolved.
+    #[cfg(feature = "tailwind")]
+    pub fn with_tailwind_defaults<I: Into<Self>>(self, defaults: I) -> Self {
+        Self {
+            tailwind: true,
+            ..self.with_defaults(defaults)
+        }
+    }
+
+    /// Returns the enabled tokens, in order and without duplicates.
+    ///
+    /// Signals are read, so this is tracked when called in a reactive context.
+    pub fn tokens(&self) -> IndexSet<String> {
+        let mut tokens = IndexMap::new();
+
+        for part in &self.parts {
+            match part {
+                ClassPart::Token(token, condition) => {
+                    tokens.insert(token.clone(), condition.get());
+                }
+                ClassPart::Signal(signal) => signal.with(|value| {
+                    for token in value.split_whitespace() {
+                        tokens.insert(token.to_string(), true);
+                    }
+                }),
+            }
+        }
+
+        let tokens = tokens
+            .into_iter()
+            .filter_map(|(token, enabled)| enabled.then_some(token))
+            .collect();
+
+        #[cfg(feature = "tailwind")]
+        if self.tailwind {
+            return crate::tailwind::merge_tailwind_tokens(tokens);
+        }
+
+        tokens
+    }
+
+    /// Returns `true` if the token is enabled.
+    pub fn contains(&self, token: &str) -> bool {
+        self.tokens().contains(token)
+    }
+
+    /// Returns `true` if no tokens are enabled.
+    pub fn is_empty(&self) -> bool {
+        self.tokens().is_empty()
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.tokens().into_iter().collect::<Vec<_>>().join(" ")
+        )
+    }
+}
+
+impl From<Option<&str>> for Class {
+    fn from(value: Option<&str>) -> Self {
+        value.map(Class::from).unwrap_or_default()
+    }
+}
+
+impl From<Option<String>> for Class {
+    fn from(value: Option<String>) -> Self {
+        value.map(Class::from).unwrap_or_default()
+    }
+}
+
+impl From<&str> for Class {
+    fn from(value: &str) -> Self {
+        Self::from_parts(token_parts(value, ClassCondition::Static(true)))
+    }
+}
+
+impl From<String> for Class {
+    fn from(value: String) -> Self {
+        Class::from(value.as_str())
+    }
+}
+
+impl<const N: usize> From<[(&str, bool); N]> for Class {
+    fn from(value: [(&str, bool); N]) -> Self {
+        Self::from_parts(
+            value
+                .into_iter()
+                .flat_map(|(tokens, enabled)| token_parts(tokens, ClassCondition::Static(enabled))),
+        )
+    }
+}
+
+impl<const N: usize> From<[(&str, Signal<bool>); N]> for Class {
+    fn from(value: [(&str, Signal<bool>); N]) -> Self {
+        Self::from_parts(
+            value
+                .into_iter()
+                .flat_map(|(tokens, enabled)| token_parts(tokens, ClassCondition::Signal(enabled))),
+        )
+    }
+}
+
+impl From<Signal<String>> for Class {
+    fn from(value: Signal<String>) -> Self {
+        Self::from_parts([ClassPart::Signal(value)])
+    }
+}
+
+impl IntoAttributeValue for Class {
+    type Output = String;
+
+    fn into_attribute_value(self) -> Self::Output {
+        self.to_string()
+    }
+}
+
+type ClassState = (ClassList, IndexSet<String>);
+
+/// Adds and removes individual tokens, so classes added by other means (e.g. `class:name=` directives) are kept.
+fn apply_tokens(class_list: &ClassList, prev: &IndexSet<String>, next: &IndexSet<String>) {
+    for token in prev.difference(next) {
+        Rndr::remove_class(class_list, token);
+    }
+    for token in next.difference(prev) {
+        Rndr::add_class(class_list, token);
+    }
+}
+
+impl IntoClass for Class {
+    type AsyncOutput = Self;
+    type State = RenderEffect<ClassState>;
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                ClassPart::Token(token, _) => token.len() + 1,
+                ClassPart::Signal(_) => 0,
+            })
+            .sum()
+    }
+
+    fn to_html(self, class: &mut String) {
+        class.push_str(&self.to_string());
+    }
+
+    fn should_overwrite(&self) -> bool {
+        true
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &leptos::tachys::renderer::types::Element,
+    ) -> Self::State {
+        let class_list = Rndr::class_list(el);
+        RenderEffect::new(move |prev: Option<ClassState>| {
+            let tokens = self.tokens();
+            match prev {
+                Some((class_list, prev)) => {
+                    apply_tokens(&class_list, &prev, &tokens);
+                    (class_list, tokens)
+                }
+                None => {
+                    if !FROM_SERVER {
+                        apply_tokens(&class_list, &IndexSet::new(), &tokens);
+                    }
+                    (class_list.clone(), tokens)
+                }
+            }
+        })
+    }
+
+    fn build(self, el: &leptos::tachys::renderer::types::Element) -> Self::State {
+        let class_list = Rndr::class_list(el);
+        RenderEffect::new(move |prev: Option<ClassState>| {
+            let tokens = self.tokens();
+            let (class_list, prev) = prev.unwrap_or_else(|| (class_list.clone(), IndexSet::new()));
+            apply_tokens(&class_list, &prev, &tokens);
+            (class_list, tokens)
+        })
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let prev_value = state.take_value();
+        *state = RenderEffect::new_with_value(
+            move |prev: Option<ClassState>| {
+                let tokens = self.tokens();
+                let (class_list, prev) = prev.expect("class state should exist on rebuild");
+                apply_tokens(&class_list, &prev, &tokens);
+                (class_list, tokens)
+            },
+            prev_value,
+        );
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn reset(state: &mut Self::State) {
+        // Replace the effect, so the removed tokens are not added again when a signal changes.
+        *state = RenderEffect::new_with_value(
+            move |prev: Option<ClassState>| {
+                let (class_list, tokens) = prev.expect("class state should exist on reset");
+                apply_tokens(&class_list, &tokens, &IndexSet::new());
+                (class_list, IndexSet::new())
+            },
+            state.take_value(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::{RwSignal, Set};
+
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!("", Class::new().to_string());
+        assert_eq!("", Class::from(None::<&str>).to_string());
+        assert_eq!("a b", Class::from("  a   b ").to_string());
+        assert_eq!("a b", Class::from("a b a".to_string()).to_string());
+        assert_eq!(
+            "a c d",
+            Class::from([("a", true), ("b", false), ("c d", true)]).to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_defaults() {
+        assert_eq!(
+            "base size-md active",
+            Class::from("size-md active")
+                .with_defaults("base size-md")
+                .to_string()
+        );
+
+        // Tokens of the class take precedence over the defaults.
+        assert_eq!(
+            "base",
+            Class::from([("active", false)])
+                .with_defaults("base active")
+                .to_string()
+        );
+        assert_eq!(
+            "base active",
+            Class::from([("active", true)])
+                .with_defaults([("base", true), ("active", false)])
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_signals() {
+        let active = RwSignal::new(false);
+        let variant = RwSignal::new("primary".to_string());
+
+        let class = Class::from(Signal::from(variant))
+            .with_defaults([("base", Signal::from(RwSignal::new(true)))])
+            .with_defaults(Class::from([("active", Signal::from(active))]));
+
+        assert_eq!("base primary", class.to_string());
+        assert!(!class.contains("active"));
+
+        active.set(true);
+        variant.set("secondary base".to_string());
+        assert_eq!("active base secondary", class.to_string());
+        assert!(class.contains("active"));
+    }
+
+    #[cfg(feature = "tailwind")]
+    #[test]
+    fn test_with_tailwind_defaults() {
+        let class = Class::from("p-4 hover:bg-blue-500")
+            .with_tailwind_defaults("flex p-2 text-sm hover:bg-gray-100")
+            .with_defaults("rounded-md");
+
+        assert_eq!(
+            "rounded-md flex text-sm p-4 hover:bg-blue-500",
+            class.to_string()
+        );
+        assert_eq!(
+            "p-2 p-4",
+            Class::from("p-4").with_defaults("p-2").to_string()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use leptos::prelude::{provide_context, use_context};
+
+use crate::{
+    parse::split_declarations,
+    scoped::{StyleRegistry, fnv1a},
+};
+
+/// Prefix of the custom property marking an element of which the style was hoisted into the style sheet.
+pub const HOISTED_STYLE_PREFIX: &str = "--ls-hoisted-";
+
+/// Options for deduplicating styles during server-side rendering.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StyleDedupeOptions {
+    /// Minimum length of the formatted declarations of a style to be hoisted.
+    pub min_length: usize,
+    /// Number of occurrences of a style after which it is hoisted. Earlier occurrences are rendered inline.
+    pub min_occurrences: usize,
+}
+
+impl Default for StyleDedupeOptions {
+    fn default() -> Self {
+        Self {
+            min_length: 32,
+            min_occurrences: 2,
+        }
+    }
+}
+
+/// Deduplicates structured styles during server-side rendering, provided through Leptos context.
+///
+/// [`IntoStyle::to_html`](leptos::tachys::html::style::IntoStyle::to_html) can only write the `style` attribute, so
+/// a repeated style is replaced by a marker custom property (e.g. `--ls-hoisted-0123456789abcdef: 1;`), which is
+/// selected by a rule in the [`StyleRegistry`]. The rules are rendered by
+/// [`ScopedStyleSheet`](crate::ScopedStyleSheet). During hydration the marker is replaced by the inline style again.
+///
+/// Until hydration, hoisted declarations have the specificity of an attribute selector instead of an inline style.
+#[derive(Clone, Debug, Default)]
+pub struct StyleDedupe {
+    options: StyleDedupeOptions,
+    registry: StyleRegistry,
+    occurrences: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl StyleDedupe {
+    /// Creates a new [`StyleDedupe`], registering hoisted rules in the registry.
+    pub fn new(options: StyleDedupeOptions, registry: StyleRegistry) -> Self {
+        Self {
+            options,
+            registry,
+            occurrences: Arc::default(),
+        }
+    }
+
+    /// Counts an occurrence of the declarations, returning the marker declaration if they should be hoisted.
+    ///
+    /// Once the style sheet has been rendered, only declarations of which the rule is already in the style sheet are
+    /// hoisted, so styles rendered afterwards (e.g. in streamed [`Suspense`](leptos::prelude::Suspense) chunks) stay
+    /// inline.
+    pub fn hoist(&self, declarations: &str) -> Option<String> {
+        if declarations.len() < self.options.min_length {
+            return None;
+        }
+
+        let mut occurrences = self
+            .occurrences
+            .lock()
+            .expect("lock should not be poisoned");
+        let count = occurrences.entry(declarations.to_string()).or_default();
+        *count += 1;
+        if *count < self.options.min_occurrences {
+            return None;
+        }
+
+        let marker = format!(
+            "{HOISTED_STYLE_PREFIX}{:016x}",
+            fnv1a(declarations.as_bytes())
+        );
+        if self.registry.is_rendered() && !self.registry.contains(&marker) {
+            return None;
+        }
+        self.registry.register(
+            &marker,
+            &format!("[style*=\"{marker}:\"] {{ {declarations} }}"),
+        );
+
+        Some(format!("{marker}: 1;"))
+    }
+}
+
+/// Enables style deduplication for server-side rendering and returns the [`StyleDedupe`].
+///
+/// Styles are rendered after their component has run, so this should be called in the root owner of the request,
+/// e.g. in the additional context of the server integration, instead of the root component. The [`StyleRegistry`]
+/// from context is reused, or a new one is provided.
+pub fn provide_style_dedupe(options: StyleDedupeOptions) -> StyleDedupe {
+    let registry = use_context::<StyleRegistry>().unwrap_or_else(|| {
+        let registry = StyleRegistry::new();
+        provide_context(registry.clone());
+        registry
+    });
+
+    let dedupe = StyleDedupe::new(options, registry);
+    provide_context(dedupe.clone());
+    dedupe
+}
+
+/// Returns the markers of hoisted styles in a server-rendered `style` attribute.
+pub(crate) fn hoisted_style_markers(style: &str) -> Vec<&str> {
+    split_declarations(style)
+        .into_iter()
+        .map(|(property, _)| property)
+        .filter(|property| property.starts_with(HOISTED_STYLE_PREFIX))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hoist() {
+        let registry = StyleRegistry::new();
+        let dedupe = StyleDedupe::new(StyleDedupeOptions::default(), registry.clone());
+        let declarations = "display: flex; padding: 0.5rem 1rem; color: red;";
+
+        assert_eq!(None, dedupe.hoist("color: red;"));
+        assert_eq!(None, dedupe.hoist("color: red;"));
+        assert_eq!(None, dedupe.hoist(declarations));
+
+        let marker = dedupe.hoist(declarations).expect("style should be hoisted");
+        assert_eq!(Some(marker.clone()), dedupe.hoist(declarations));
+        assert!(marker.starts_with(HOISTED_STYLE_PREFIX));
+
+        let property = marker.trim_end_matches(": 1;");
+        assert_eq!(
+            format!("[style*=\"{property}:\"] {{ {declarations} }}"),
+            registry.to_css()
+        );
+        assert_eq!(vec![property], hoisted_style_markers(&marker));
+    }
+
+    #[test]
+    fn test_hoist_after_render() {
+        let registry = StyleRegistry::new();
+        let dedupe = StyleDedupe::new(StyleDedupeOptions::default(), registry.clone());
+        let declarations = "display: flex; padding: 0.5rem 1rem; color: red;";
+        let later = "display: grid; padding: 0.5rem 1rem; color: blue;";
+
+        assert_eq!(None, dedupe.hoist(declarations));
+        let marker = dedupe.hoist(declarations);
+        assert!(marker.is_some());
+
+        registry.render();
+        assert_eq!(marker, dedupe.hoist(declarations));
+        assert_eq!(None, dedupe.hoist(later));
+        assert_eq!(None, dedupe.hoist(later));
+        assert!(!registry.to_css().contains(later));
+    }
+
+    #[test]
+    fn test_hoisted_style_markers() {
+        assert_eq!(Vec::<&str>::new(), hoisted_style_markers("color: red;"));
+        assert_eq!(
+            vec!["--ls-hoisted-0123456789abcdef"],
+            hoisted_style_markers("--ls-hoisted-0123456789abcdef: 1; color: red;")
+        );
+    }
+}
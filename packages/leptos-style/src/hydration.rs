@@ -0,0 +1,178 @@
+use indexmap::IndexMap;
+
+use crate::{
+    parse::{normalize_value, split_declarations},
+    property::normalize_property_name,
+};
+
+/// Options for validating server-rendered styles during hydration, provided through Leptos context.
+///
+/// Validation only runs in debug builds. Without a context, mismatches are logged, but not patched.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StyleHydrationOptions {
+    /// Log a warning for each element where the server-rendered style does not match the client style.
+    pub warn: bool,
+    /// Set the client value of mismatched properties on the element.
+    pub patch: bool,
+}
+
+impl Default for StyleHydrationOptions {
+    fn default() -> Self {
+        Self {
+            warn: true,
+            patch: false,
+        }
+    }
+}
+
+/// A property of which the server-rendered value differs from the client value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HydrationMismatch {
+    pub property: String,
+    /// Value rendered by the server, `None` if the property was missing.
+    pub server: Option<String>,
+    pub client: String,
+}
+
+fn declaration_map(style: &str) -> IndexMap<String, String> {
+    split_declarations(style)
+        .into_iter()
+        .map(|(property, value)| (normalize_property_name(property), value.to_string()))
+        .collect()
+}
+
+/// Returns `true` if the values are equal after normalizing whitespace.
+///
+/// Keywords are compared case-insensitively, unless the values contain strings or URLs, which are case-sensitive.
+fn values_match(server: &str, client: &str) -> bool {
+    let (server, client) = (normalize_value(server), normalize_value(client));
+    let is_case_sensitive =
+        |value: &str| value.contains(['"', '\'']) || value.to_ascii_lowercase().contains("url(");
+
+    server == client
+        || (!is_case_sensitive(&server)
+            && !is_case_sensitive(&client)
+            && server.eq_ignore_ascii_case(&client))
+}
+
+/// Compares a server-rendered `style` attribute with the client style.
+///
+/// Both styles are parsed, and property names and values are normalized, so differences in whitespace, keyword case
+/// and ordering are ignored.
+/// Properties only present on the server are not reported, as they may be set by `style:prop=` directives.
+pub fn hydration_mismatches(server: &str, client: &str) -> Vec<HydrationMismatch> {
+    let server = declaration_map(server);
+
+    declaration_map(client)
+        .into_iter()
+        .filter_map(|(property, client)| {
+            let server = server.get(&property);
+            (!server.is_some_and(|server| values_match(server, &client))).then(|| {
+                HydrationMismatch {
+                    server: server.cloned(),
+                    property,
+                    client,
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn validate_hydration(
+    el: &leptos::tachys::renderer::types::Element,
+    style: &crate::Style,
+    declarations: &crate::style::AppliedDeclarations,
+) {
+    use leptos::prelude::use_context;
+
+    let options = use_context::<StyleHydrationOptions>().unwrap_or_default();
+    if !options.warn && !options.patch {
+        return;
+    }
+
+    let server = el.get_attribute("style").unwrap_or_default();
+    let client = style.to_string();
+    let mismatches = hydration_mismatches(&server, &client);
+    if mismatches.is_empty() {
+        return;
+    }
+
+    if options.warn {
+        leptos::logging::warn!(
+            "Style hydration mismatch on <{}>: server rendered `{}`, client expected `{}` (mismatched properties: {}).",
+            el.tag_name().to_lowercase(),
+            server,
+            client,
+            mismatches
+                .iter()
+                .map(|mismatch| mismatch.property.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+    }
+
+    if options.patch {
+        let mut matched = declarations.clone();
+        matched.retain(|(property, _)| {
+            !mismatches
+                .iter()
+                .any(|mismatch| &mismatch.property == property)
+        });
+        crate::style::apply_declarations(el, &matched, declarations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hydration_mismatches() {
+        assert_eq!(
+            Vec::<HydrationMismatch>::new(),
+            hydration_mismatches(
+                "color:red;  padding: 0.5rem",
+                "padding: 0.5rem; color: red;"
+            )
+        );
+
+        assert_eq!(
+            Vec::<HydrationMismatch>::new(),
+            hydration_mismatches(
+                "border: 1px  solid RED; font-family: \"Fira Sans\" , serif",
+                "border: 1px solid red; font-family: \"Fira Sans\", serif;"
+            )
+        );
+        assert_eq!(
+            vec![HydrationMismatch {
+                property: "content".to_string(),
+                server: Some("\"A\"".to_string()),
+                client: "\"a\"".to_string(),
+            }],
+            hydration_mismatches("content: \"A\"", "content: \"a\"")
+        );
+
+        // Properties from `style:prop=` directives are ignored.
+        assert_eq!(
+            Vec::<HydrationMismatch>::new(),
+            hydration_mismatches("color: red; opacity: 0.5;", "color: red;")
+        );
+
+        assert_eq!(
+            vec![
+                HydrationMismatch {
+                    property: "color".to_string(),
+                    server: Some("red".to_string()),
+                    client: "blue".to_string(),
+                },
+                HydrationMismatch {
+                    property: "padding".to_string(),
+                    server: None,
+                    client: "0.5rem".to_string(),
+                },
+            ],
+            hydration_mismatches("color: red;", "color: blue; padding: 0.5rem;")
+        );
+    }
+}
@@ -1,5 +1,33 @@
 //! Style for [Yew](https://yew.rs/) components.
 
+mod class;
+mod dedupe;
+mod hydration;
+mod parse;
+mod property;
+mod reactive;
+mod responsive;
+mod sanitize;
+mod scoped;
+#[cfg(feature = "serde")]
+mod serialization;
+mod shorthand;
 mod style;
+#[cfg(feature = "tailwind")]
+mod tailwind;
+mod transition;
 
+pub use crate::class::*;
+pub use crate::dedupe::*;
+pub use crate::hydration::*;
+pub use crate::parse::*;
+pub use crate::property::*;
+pub use crate::reactive::*;
+pub use crate::responsive::*;
+pub use crate::sanitize::*;
+pub use crate::scoped::*;
+pub use crate::shorthand::*;
 pub use crate::style::*;
+#[cfg(feature = "tailwind")]
+pub use crate::tailwind::*;
+pub use crate::transition::*;
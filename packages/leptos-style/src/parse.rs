@@ -0,0 +1,365 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::sanitize::is_valid_property_name;
+
+/// Kind of a [`ParseError`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// A declaration has no colon separating the property name and value.
+    MissingColon,
+    /// A declaration has no property name.
+    MissingPropertyName,
+    /// A declaration of the property has no value.
+    MissingValue(String),
+    /// The property name contains characters that are not allowed in a CSS property name.
+    InvalidPropertyName(String),
+    /// A string is not terminated.
+    UnterminatedString,
+    /// A parenthesis is not closed.
+    UnclosedParenthesis,
+    /// A character is not allowed outside of a string.
+    UnexpectedCharacter(char),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColon => write!(f, "missing colon in declaration"),
+            Self::MissingPropertyName => write!(f, "missing property name in declaration"),
+            Self::MissingValue(property) => write!(f, "missing value of property `{property}`"),
+            Self::InvalidPropertyName(property) => {
+                write!(f, "invalid property name `{property}`")
+            }
+            Self::UnterminatedString => write!(f, "unterminated string"),
+            Self::UnclosedParenthesis => write!(f, "unclosed parenthesis"),
+            Self::UnexpectedCharacter(character) => {
+                write!(f, "unexpected character `{character}`")
+            }
+        }
+    }
+}
+
+/// Error returned when a style string can not be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+}
+
+impl ParseError {
+    fn new(style: &str, offset: usize, kind: ParseErrorKind) -> Self {
+        let before = &style[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            kind,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Splits a style string into its declarations.
+///
+/// Semicolons inside strings and parentheses (e.g. `url("a;b")`) do not end a declaration. Empty declarations and
+/// declarations without a colon are skipped. Property names and values are trimmed, but otherwise returned as written.
+pub(crate) fn split_declarations(style: &str) -> Vec<(&str, &str)> {
+    let mut declarations = vec![];
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, char) in style.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match char {
+            '\\' => escaped = true,
+            '"' | '\'' => match quote {
+                Some(open) if open == char => quote = None,
+                None => quote = Some(char),
+                _ => {}
+            },
+            '(' if quote.is_none() => depth += 1,
+            ')' if quote.is_none() => depth = depth.saturating_sub(1),
+            ';' if quote.is_none() && depth == 0 => {
+                declarations.extend(split_declaration(&style[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.extend(split_declaration(&style[start..]));
+
+    declarations
+}
+
+fn split_declaration(declaration: &str) -> Option<(&str, &str)> {
+    let (property, value) = declaration.split_once(':')?;
+    let (property, value) = (property.trim(), value.trim());
+
+    (!property.is_empty() && !value.is_empty()).then_some((property, value))
+}
+
+/// Parses a style string into its declarations, returning an error for malformed declarations.
+///
+/// Empty declarations are allowed, e.g. a trailing semicolon. Property names and values are trimmed.
+pub(crate) fn parse_declarations(style: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    let mut declarations = vec![];
+    let mut quote: Option<(char, usize)> = None;
+    let mut parentheses = vec![];
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, char) in style.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match char {
+            '\\' => escaped = true,
+            '"' | '\'' => match quote {
+                Some((open, _)) if open == char => quote = None,
+                None => quote = Some((char, index)),
+                _ => {}
+            },
+            _ if quote.is_some() => {}
+            '(' => parentheses.push(index),
+            ')' if parentheses.pop().is_none() => {
+                return Err(ParseError::new(
+                    style,
+                    index,
+                    ParseErrorKind::UnexpectedCharacter(char),
+                ));
+            }
+            '{' | '}' => {
+                return Err(ParseError::new(
+                    style,
+                    index,
+                    ParseErrorKind::UnexpectedCharacter(char),
+                ));
+            }
+            ';' if parentheses.is_empty() => {
+                declarations.extend(parse_declaration(style, start, index)?);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((_, index)) = quote {
+        return Err(ParseError::new(
+            style,
+            index,
+            ParseErrorKind::UnterminatedString,
+        ));
+    }
+    if let Some(index) = parentheses.first() {
+        return Err(ParseError::new(
+            style,
+            *index,
+            ParseErrorKind::UnclosedParenthesis,
+        ));
+    }
+    declarations.extend(parse_declaration(style, start, style.len())?);
+
+    Ok(declarations)
+}
+
+fn parse_declaration(
+    style: &str,
+    start: usize,
+    end: usize,
+) -> Result<Option<(&str, &str)>, ParseError> {
+    let declaration = &style[start..end];
+    let trimmed = declaration.trim_start();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let property_start = start + declaration.len() - trimmed.len();
+
+    let Some(colon) = declaration.find(':') else {
+        return Err(ParseError::new(
+            style,
+            property_start,
+            ParseErrorKind::MissingColon,
+        ));
+    };
+
+    let property = declaration[..colon].trim();
+    if property.is_empty() {
+        return Err(ParseError::new(
+            style,
+            property_start,
+            ParseErrorKind::MissingPropertyName,
+        ));
+    }
+    if !is_valid_property_name(property) {
+        return Err(ParseError::new(
+            style,
+            property_start,
+            ParseErrorKind::InvalidPropertyName(property.to_string()),
+        ));
+    }
+
+    let value = declaration[colon + 1..].trim();
+    if value.is_empty() {
+        return Err(ParseError::new(
+            style,
+            start + colon,
+            ParseErrorKind::MissingValue(property.to_string()),
+        ));
+    }
+
+    Ok(Some((property, value)))
+}
+
+/// Normalizes whitespace in a value for comparison.
+///
+/// Whitespace outside of strings is collapsed and removed around commas, parentheses and `!`.
+pub(crate) fn normalize_value(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut space = false;
+
+    for char in value.trim().chars() {
+        if escaped || quote.is_some() {
+            normalized.push(char);
+            if escaped {
+                escaped = false;
+            } else if char == '\\' {
+                escaped = true;
+            } else if Some(char) == quote {
+                quote = None;
+            }
+            continue;
+        }
+
+        if char.is_whitespace() {
+            space = true;
+            continue;
+        }
+
+        if space && !matches!(char, ',' | ')' | '!') && !normalized.ends_with([',', '(', '!']) {
+            normalized.push(' ');
+        }
+        space = false;
+
+        match char {
+            '\\' => escaped = true,
+            '"' | '\'' => quote = Some(char),
+            _ => {}
+        }
+        normalized.push(char);
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_declarations() {
+        assert_eq!(Vec::<(&str, &str)>::new(), split_declarations(""));
+        assert_eq!(Vec::<(&str, &str)>::new(), split_declarations(" ; ;"));
+        assert_eq!(vec![("color", "red")], split_declarations("color: red"));
+        assert_eq!(
+            vec![("color", "red"), ("padding", "0.5rem !important")],
+            split_declarations(" color:red;  padding : 0.5rem !important; ")
+        );
+        assert_eq!(
+            vec![
+                ("background", "url(\"a;b.png\")"),
+                ("content", "';'"),
+                ("background-image", "url(data:image/png;base64,abc)"),
+            ],
+            split_declarations(
+                "background: url(\"a;b.png\"); content: ';'; background-image: url(data:image/png;base64,abc);"
+            )
+        );
+        assert_eq!(
+            vec![("color", "red")],
+            split_declarations("invalid; color: red; padding:;")
+        );
+    }
+
+    #[test]
+    fn test_parse_declarations() {
+        assert_eq!(Ok(vec![]), parse_declarations(" ; "));
+        assert_eq!(
+            Ok(vec![("color", "red"), ("background", "url(\"a;b.png\")")]),
+            parse_declarations("color: red;\n  background: url(\"a;b.png\");")
+        );
+
+        let error = |kind, line, column| Err(ParseError { kind, line, column });
+        assert_eq!(
+            error(ParseErrorKind::MissingColon, 2, 3),
+            parse_declarations("color: red;\n  padding 1rem;")
+        );
+        assert_eq!(
+            error(ParseErrorKind::MissingPropertyName, 1, 2),
+            parse_declarations(" : red")
+        );
+        assert_eq!(
+            error(ParseErrorKind::MissingValue("color".to_string()), 1, 6),
+            parse_declarations("color: ;")
+        );
+        assert_eq!(
+            error(
+                ParseErrorKind::InvalidPropertyName("col or".to_string()),
+                1,
+                1
+            ),
+            parse_declarations("col or: red")
+        );
+        assert_eq!(
+            error(ParseErrorKind::UnterminatedString, 1, 10),
+            parse_declarations("content: 'abc")
+        );
+        assert_eq!(
+            error(ParseErrorKind::UnclosedParenthesis, 2, 16),
+            parse_declarations("color: red;\nbackground: url(a.png")
+        );
+        assert_eq!(
+            error(ParseErrorKind::UnexpectedCharacter('}'), 1, 12),
+            parse_declarations("color: red;} body { color: blue")
+        );
+        assert_eq!(
+            error(ParseErrorKind::UnexpectedCharacter(')'), 1, 11),
+            parse_declarations("color: red)")
+        );
+    }
+
+    #[test]
+    fn test_normalize_value() {
+        assert_eq!("1px solid red", normalize_value("  1px \n solid\tred "));
+        assert_eq!("rgb(0,0,0)", normalize_value("rgb( 0, 0,  0 )"));
+        assert_eq!("red!important", normalize_value("red ! important"));
+        assert_eq!("'a  b' c", normalize_value("'a  b'   c"));
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::{self, Display};
+
+use indexmap::IndexMap;
+
+const VENDOR_PREFIXES: [&str; 4] = ["webkit-", "moz-", "ms-", "o-"];
+
+/// Normalizes a CSS property name to lowercase kebab-case.
+///
+/// Custom properties (`--*`) are case-sensitive and returned untouched. Names already containing a hyphen are only
+/// lowercased. Other names are treated as camelCase, as used in JavaScript, so `backgroundColor` becomes
+/// `background-color` and `WebkitTransform` or `msTransform` become `-webkit-transform` and `-ms-transform`.
+pub fn normalize_property_name(name: &str) -> String {
+    let name = name.trim();
+
+    if name.starts_with("--") {
+        return name.to_string();
+    }
+
+    if name.contains('-') {
+        return name.to_ascii_lowercase();
+    }
+
+    let mut normalized = String::with_capacity(name.len() + 4);
+    let mut previous_is_lowercase = false;
+    for char in name.chars() {
+        if char.is_ascii_uppercase() {
+            if previous_is_lowercase {
+                normalized.push('-');
+            }
+            normalized.push(char.to_ascii_lowercase());
+            previous_is_lowercase = false;
+        } else {
+            normalized.push(char);
+            previous_is_lowercase = char.is_ascii_lowercase() || char.is_ascii_digit();
+        }
+    }
+
+    if VENDOR_PREFIXES
+        .iter()
+        .any(|prefix| normalized.starts_with(prefix))
+    {
+        normalized.insert(0, '-');
+    }
+
+    normalized
+}
+
+pub(crate) fn normalize_style_map(
+    map: IndexMap<String, Option<String>>,
+) -> IndexMap<String, Option<String>> {
+    let mut normalized = IndexMap::with_capacity(map.len());
+    for (key, value) in map {
+        normalized.insert(normalize_property_name(&key), value);
+    }
+    normalized
+}
+
+/// A CSS vendor prefix.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VendorPrefix {
+    Webkit,
+    Moz,
+    Ms,
+    O,
+}
+
+impl VendorPrefix {
+    /// Returns the prefix including the leading and trailing hyphen, e.g. `-webkit-`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Webkit => "-webkit-",
+            Self::Moz => "-moz-",
+            Self::Ms => "-ms-",
+            Self::O => "-o-",
+        }
+    }
+
+    /// Prefixes a property name.
+    pub fn apply(&self, property: &str) -> String {
+        format!("{}{}", self.as_str(), property)
+    }
+}
+
+impl Display for VendorPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+pub(crate) fn expand_vendor_prefixes(
+    map: IndexMap<String, Option<String>>,
+    properties: &IndexMap<String, Vec<VendorPrefix>>,
+) -> IndexMap<String, Option<String>> {
+    let mut expanded = IndexMap::with_capacity(map.len());
+    for (key, value) in map {
+        if let Some(prefixes) = properties.get(&key) {
+            for prefix in prefixes {
+                let prefixed_key = prefix.apply(&key);
+                if !expanded.contains_key(&prefixed_key) {
+                    expanded.insert(prefixed_key, value.clone());
+                }
+            }
+        }
+        expanded.insert(key, value);
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_property_name() {
+        assert_eq!("color", normalize_property_name("color"));
+        assert_eq!("color", normalize_property_name("Color"));
+        assert_eq!("color", normalize_property_name("COLOR"));
+        assert_eq!("color", normalize_property_name(" color "));
+        assert_eq!(
+            "background-color",
+            normalize_property_name("backgroundColor")
+        );
+        assert_eq!(
+            "background-color",
+            normalize_property_name("background-color")
+        );
+        assert_eq!(
+            "background-color",
+            normalize_property_name("Background-Color")
+        );
+        assert_eq!(
+            "border-top-left-radius",
+            normalize_property_name("borderTopLeftRadius")
+        );
+
+        // Vendor prefixes
+        assert_eq!(
+            "-webkit-transform",
+            normalize_property_name("WebkitTransform")
+        );
+        assert_eq!(
+            "-webkit-transform",
+            normalize_property_name("webkitTransform")
+        );
+        assert_eq!("-moz-appearance", normalize_property_name("MozAppearance"));
+        assert_eq!("-ms-transform", normalize_property_name("msTransform"));
+        assert_eq!(
+            "-webkit-transform",
+            normalize_property_name("-webkit-transform")
+        );
+
+        // Custom properties
+        assert_eq!("--mainColor", normalize_property_name("--mainColor"));
+        assert_eq!("--main-color", normalize_property_name("--main-color"));
+    }
+
+    #[test]
+    fn test_expand_vendor_prefixes() {
+        let properties = IndexMap::from([(
+            "user-select".to_string(),
+            vec![VendorPrefix::Webkit, VendorPrefix::Moz],
+        )]);
+
+        assert_eq!(
+            IndexMap::from([
+                ("color".to_string(), Some("red".to_string())),
+                ("-webkit-user-select".to_string(), Some("none".to_string())),
+                ("-moz-user-select".to_string(), Some("none".to_string())),
+                ("user-select".to_string(), Some("none".to_string())),
+            ]),
+            expand_vendor_prefixes(
+                IndexMap::from([
+                    ("color".to_string(), Some("red".to_string())),
+                    ("user-select".to_string(), Some("none".to_string())),
+                ]),
+                &properties,
+            ),
+        );
+
+        // Explicit prefixed declarations are preserved.
+        assert_eq!(
+            IndexMap::from([
+                ("-webkit-user-select".to_string(), Some("text".to_string())),
+                ("-moz-user-select".to_string(), Some("none".to_string())),
+                ("user-select".to_string(), Some("none".to_string())),
+            ]),
+            expand_vendor_prefixes(
+                IndexMap::from([
+                    ("-webkit-user-select".to_string(), Some("text".to_string())),
+                    ("user-select".to_string(), Some("none".to_string())),
+                ]),
+                &properties,
+            ),
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use indexmap::IndexMap;
+use leptos::{
+    attr::IntoAttributeValue,
+    prelude::{Get, GetUntracked, MaybeProp, Memo, Signal},
+    tachys::html::style::IntoStyle,
+};
+
+use crate::style::Style;
+
+/// A [`Style`] which is static or derived from reactive sources, such as closures, signals and memos.
+///
+/// Use it as component prop type (`#[prop(into)] style: ReactiveStyle`) to accept both static and reactive styles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReactiveStyle(Signal<Style>);
+
+impl ReactiveStyle {
+    /// Returns the current style, tracking the reactive sources.
+    pub fn get(&self) -> Style {
+        self.0.get()
+    }
+
+    /// Returns the current style, without tracking the reactive sources.
+    pub fn get_untracked(&self) -> Style {
+        self.0.get_untracked()
+    }
+
+    /// Merges the style with defaults, see [`Style::with_defaults`].
+    ///
+    /// The result is derived from both styles, so it is updated when either of them changes.
+    pub fn with_defaults<I: Into<Self>>(self, defaults: I) -> Self {
+        let defaults = defaults.into();
+        Self(Signal::derive(move || {
+            self.get().with_defaults(defaults.get())
+        }))
+    }
+}
+
+impl Default for ReactiveStyle {
+    fn default() -> Self {
+        Self::from(Style::default())
+    }
+}
+
+impl From<Style> for ReactiveStyle {
+    fn from(value: Style) -> Self {
+        Self(Signal::stored(value))
+    }
+}
+
+macro_rules! static_into_reactive_style {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for ReactiveStyle {
+                fn from(value: $ty) -> Self {
+                    Self::from(Style::from(value))
+                }
+            }
+        )*
+    };
+}
+
+static_into_reactive_style!(
+    Option<&str>,
+    Option<String>,
+    &str,
+    String,
+    IndexMap<String, Option<String>>,
+    IndexMap<String, String>
+);
+
+impl<const N: usize> From<[(&str, &str); N]> for ReactiveStyle {
+    fn from(value: [(&str, &str); N]) -> Self {
+        Self::from(Style::from(value))
+    }
+}
+
+impl<const N: usize> From<[(&str, String); N]> for ReactiveStyle {
+    fn from(value: [(&str, String); N]) -> Self {
+        Self::from(Style::from(value))
+    }
+}
+
+impl<const N: usize> From<[(&str, Option<&str>); N]> for ReactiveStyle {
+    fn from(value: [(&str, Option<&str>); N]) -> Self {
+        Self::from(Style::from(value))
+    }
+}
+
+impl<const N: usize> From<[(&str, Option<String>); N]> for ReactiveStyle {
+    fn from(value: [(&str, Option<String>); N]) -> Self {
+        Self::from(Style::from(value))
+    }
+}
+
+impl<F, S> From<F> for ReactiveStyle
+where
+    F: Fn() -> S + Send + Sync + 'static,
+    S: Into<Style>,
+{
+    fn from(value: F) -> Self {
+        Self(Signal::derive(move || value().into()))
+    }
+}
+
+impl From<Signal<Style>> for ReactiveStyle {
+    fn from(value: Signal<Style>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Memo<Style>> for ReactiveStyle {
+    fn from(value: Memo<Style>) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Signal<String>> for ReactiveStyle {
+    fn from(value: Signal<String>) -> Self {
+        Self(Signal::derive(move || Style::from(value.get())))
+    }
+}
+
+impl From<MaybeProp<String>> for ReactiveStyle {
+    fn from(value: MaybeProp<String>) -> Self {
+        Self(Signal::derive(move || Style::from(value.get())))
+    }
+}
+
+impl IntoAttributeValue for ReactiveStyle {
+    type Output = Signal<String>;
+
+    fn into_attribute_value(self) -> Self::Output {
+        Signal::derive(move || self.get().to_string())
+    }
+}
+
+impl IntoStyle for ReactiveStyle {
+    type AsyncOutput = Self;
+    type State = <Signal<Style> as IntoStyle>::State;
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn to_html(self, style: &mut String) {
+        self.0.to_html(style);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &leptos::tachys::renderer::types::Element,
+    ) -> Self::State {
+        self.0.hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &leptos::tachys::renderer::types::Element) -> Self::State {
+        self.0.build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.0.rebuild(state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn reset(state: &mut Self::State) {
+        <Signal<Style> as IntoStyle>::reset(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::{RwSignal, Set};
+
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(
+            Style::from("color: red;"),
+            ReactiveStyle::from("color: red;").get()
+        );
+        assert_eq!(
+            Style::from([("color", "red")]),
+            ReactiveStyle::from([("color", "red")]).get()
+        );
+        assert_eq!(Style::default(), ReactiveStyle::default().get());
+        assert_eq!(
+            Style::default(),
+            ReactiveStyle::from(MaybeProp::<String>::default()).get()
+        );
+    }
+
+    #[test]
+    fn test_reactive_sources() {
+        let color = RwSignal::new("red");
+
+        let closure = ReactiveStyle::from(move || format!("color: {};", color.get()));
+        let signal = ReactiveStyle::from(Signal::derive(move || {
+            Style::from([("color", color.get())])
+        }));
+        let string_signal =
+            ReactiveStyle::from(Signal::derive(move || format!("color: {};", color.get())));
+        let maybe_prop = ReactiveStyle::from(MaybeProp::derive(move || {
+            Some(format!("color: {};", color.get()))
+        }));
+
+        color.set("blue");
+        assert_eq!(Style::from("color: blue;"), closure.get());
+        assert_eq!(Style::from([("color", "blue")]), signal.get());
+        assert_eq!(Style::from("color: blue;"), string_signal.get());
+        assert_eq!(Style::from("color: blue;"), maybe_prop.get());
+    }
+
+    #[test]
+    fn test_with_defaults() {
+        let padding = RwSignal::new("1rem");
+        let color = RwSignal::new("red");
+
+        let style = ReactiveStyle::from(move || [("color", color.get())])
+            .with_defaults(move || [("color", "black"), ("padding", padding.get())]);
+
+        assert_eq!(
+            Style::from([("padding", "1rem"), ("color", "red")]),
+            style.get()
+        );
+
+        padding.set("2rem");
+        color.set("blue");
+        assert_eq!(
+            Style::from([("padding", "2rem"), ("color", "blue")]),
+            style.get()
+        );
+    }
+}
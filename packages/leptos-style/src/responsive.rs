@@ -0,0 +1,331 @@
+use indexmap::IndexMap;
+use leptos::prelude::{provide_context, use_context};
+
+use crate::{
+    property::normalize_property_name,
+    scoped::{ScopedStyle, use_scoped_style},
+    style::Style,
+};
+
+/// Breakpoint name of the value applied at all viewport widths.
+pub const BASE_BREAKPOINT: &str = "base";
+
+/// Named viewport breakpoints mapped to their minimum width, in ascending order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoints(IndexMap<String, String>);
+
+impl Breakpoints {
+    /// Creates new [`Breakpoints`] from names and minimum widths, which should be in ascending order.
+    pub fn new<K: Into<String>, V: Into<String>>(
+        breakpoints: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        Self(
+            breakpoints
+                .into_iter()
+                .map(|(name, min_width)| (name.into(), min_width.into()))
+                .collect(),
+        )
+    }
+
+    /// Returns the minimum width of the breakpoint.
+    pub fn min_width(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Returns the media query at-rule of the breakpoint.
+    pub fn media_query(&self, name: &str) -> Option<String> {
+        self.min_width(name)
+            .map(|min_width| format!("@media (min-width: {min_width})"))
+    }
+}
+
+/// The breakpoints of Tailwind CSS.
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self::new([
+            ("sm", "640px"),
+            ("md", "768px"),
+            ("lg", "1024px"),
+            ("xl", "1280px"),
+            ("2xl", "1536px"),
+        ])
+    }
+}
+
+/// Provides [`Breakpoints`] through context.
+pub fn provide_breakpoints(breakpoints: Breakpoints) {
+    provide_context(breakpoints);
+}
+
+/// Returns the [`Breakpoints`] from context, or the default breakpoints.
+pub fn use_breakpoints() -> Breakpoints {
+    use_context::<Breakpoints>().unwrap_or_default()
+}
+
+/// A value by breakpoint name.
+///
+/// The [`BASE_BREAKPOINT`] value applies at all viewport widths. Names starting with `@` are used as at-rule instead of a
+/// breakpoint, e.g. `@media (orientation: landscape)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResponsiveValue(IndexMap<String, String>);
+
+impl ResponsiveValue {
+    /// Returns the value applied at all viewport widths.
+    pub fn base(&self) -> Option<&str> {
+        self.0.get(BASE_BREAKPOINT).map(String::as_str)
+    }
+
+    /// Returns `true` if the value has values other than the base value.
+    pub fn is_responsive(&self) -> bool {
+        self.0.keys().any(|name| name != BASE_BREAKPOINT)
+    }
+}
+
+impl From<&str> for ResponsiveValue {
+    fn from(value: &str) -> Self {
+        Self(IndexMap::from([(
+            BASE_BREAKPOINT.to_string(),
+            value.to_string(),
+        )]))
+    }
+}
+
+impl From<String> for ResponsiveValue {
+    fn from(value: String) -> Self {
+        Self(IndexMap::from([(BASE_BREAKPOINT.to_string(), value)]))
+    }
+}
+
+impl From<IndexMap<String, String>> for ResponsiveValue {
+    fn from(value: IndexMap<String, String>) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for ResponsiveValue {
+    fn from(value: [(&str, &str); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl<const N: usize> From<[(&str, String); N]> for ResponsiveValue {
+    fn from(value: [(&str, String); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+}
+
+/// Marks a value `!important`, unless it already is.
+fn important(value: &str) -> String {
+    let value = value.trim_end();
+    let is_important = value
+        .rfind('!')
+        .is_some_and(|index| value[index + 1..].trim().eq_ignore_ascii_case("important"));
+
+    if is_important {
+        value.to_string()
+    } else {
+        format!("{value} !important")
+    }
+}
+
+/// A structured style of which values can vary by viewport width.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResponsiveStyle(IndexMap<String, ResponsiveValue>);
+
+impl ResponsiveStyle {
+    /// Creates a new [`ResponsiveStyle`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of a property.
+    pub fn set<K: AsRef<str>, V: Into<ResponsiveValue>>(mut self, property: K, value: V) -> Self {
+        self.0
+            .insert(normalize_property_name(property.as_ref()), value.into());
+        self
+    }
+
+    /// Splits the style into an inline style and a scoped style.
+    ///
+    /// Base values are inlined and the values for other breakpoints are moved to media queries in the scoped style.
+    /// Inline styles take precedence over class rules, so the values in media queries are marked `!important`. Unknown
+    /// breakpoints are ignored.
+    pub fn resolve(&self, breakpoints: &Breakpoints) -> (Style, Option<ScopedStyle>) {
+        let mut inline = IndexMap::new();
+        let mut rules: IndexMap<String, IndexMap<String, Option<String>>> = IndexMap::new();
+
+        for (property, value) in &self.0 {
+            for (name, value) in &value.0 {
+                if name == BASE_BREAKPOINT {
+                    inline.insert(property.clone(), Some(value.clone()));
+                } else if name.starts_with('@') || breakpoints.min_width(name).is_some() {
+                    rules
+                        .entry(name.clone())
+                        .or_default()
+                        .insert(property.clone(), Some(important(value)));
+                }
+            }
+        }
+
+        // Media queries are emitted in breakpoint order, so larger breakpoints take precedence.
+        rules.sort_by_cached_key(|name, _| {
+            breakpoints
+                .0
+                .get_index_of(name)
+                .unwrap_or(breakpoints.0.len())
+        });
+
+        let scoped_style = (!rules.is_empty()).then(|| {
+            rules.into_iter().fold(
+                ScopedStyle::default(),
+                |scoped_style, (name, declarations)| {
+                    let at_rule = breakpoints.media_query(&name).unwrap_or(name);
+                    scoped_style.rule(&at_rule, declarations)
+                },
+            )
+        });
+
+        let inline = if inline.is_empty() {
+            Style::default()
+        } else {
+            Style::from(inline)
+        };
+
+        (inline, scoped_style)
+    }
+}
+
+impl<const N: usize, V: Into<ResponsiveValue>> From<[(&str, V); N]> for ResponsiveStyle {
+    fn from(value: [(&str, V); N]) -> Self {
+        value
+            .into_iter()
+            .fold(Self::new(), |style, (property, value)| {
+                style.set(property, value)
+            })
+    }
+}
+
+/// A responsive style resolved into an inline style and a generated class.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResolvedResponsiveStyle {
+    /// Inline style for the `style` attribute.
+    pub style: Style,
+    /// Generated class for the `class` attribute, `None` if no property has values for other breakpoints.
+    pub class: Option<String>,
+}
+
+/// Resolves a responsive style using the [`Breakpoints`] from context, registering the scoped style.
+///
+/// See [`ResponsiveStyle::resolve`] and [`use_scoped_style`].
+pub fn use_responsive_style<S: Into<ResponsiveStyle>>(style: S) -> ResolvedResponsiveStyle {
+    let (style, scoped_style) = style.into().resolve(&use_breakpoints());
+
+    ResolvedResponsiveStyle {
+        style,
+        class: scoped_style.map(use_scoped_style),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakpoints() {
+        let breakpoints = Breakpoints::default();
+        assert_eq!(Some("768px"), breakpoints.min_width("md"));
+        assert_eq!(
+            Some("@media (min-width: 768px)".to_string()),
+            breakpoints.media_query("md")
+        );
+        assert_eq!(None, breakpoints.media_query("unknown"));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let (style, scoped_style) = ResponsiveStyle::from([
+            ("color", ResponsiveValue::from("red")),
+            (
+                "padding",
+                ResponsiveValue::from([("lg", "3rem"), ("base", "1rem"), ("md", "2rem")]),
+            ),
+            (
+                "marginTop",
+                ResponsiveValue::from([("md", "1rem"), ("unknown", "2rem")]),
+            ),
+            ("display", ResponsiveValue::from([("@media print", "none")])),
+        ])
+        .resolve(&Breakpoints::default());
+
+        assert_eq!(Style::from([("color", "red"), ("padding", "1rem")]), style);
+        assert_eq!(
+            Some(
+                ScopedStyle::default()
+                    .rule(
+                        "@media (min-width: 768px)",
+                        [
+                            ("padding", "2rem !important"),
+                            ("margin-top", "1rem !important")
+                        ]
+                    )
+                    .rule(
+                        "@media (min-width: 1024px)",
+                        [("padding", "3rem !important")]
+                    )
+                    .rule("@media print", [("display", "none !important")])
+            ),
+            scoped_style
+        );
+    }
+
+    #[test]
+    fn test_resolve_custom_breakpoints() {
+        let (style, scoped_style) = ResponsiveStyle::new()
+            .set("gap", [("base", "0"), ("tablet", "1rem")])
+            .resolve(&Breakpoints::new([("tablet", "600px")]));
+
+        assert_eq!(Style::from([("gap", "0")]), style);
+        assert_eq!(
+            Some(
+                ScopedStyle::default()
+                    .rule("@media (min-width: 600px)", [("gap", "1rem !important")])
+            ),
+            scoped_style
+        );
+
+        let scoped_style = scoped_style.expect("scoped style should exist");
+        assert_eq!(
+            format!(
+                "@media (min-width: 600px) {{ .{} {{ gap: 1rem !important; }} }}",
+                scoped_style.class_name()
+            ),
+            scoped_style.to_css()
+        );
+    }
+
+    #[test]
+    fn test_important() {
+        assert_eq!("1rem !important", important("1rem"));
+        assert_eq!("1rem !important", important("1rem !important"));
+        assert_eq!("1rem!IMPORTANT", important("1rem!IMPORTANT "));
+    }
+
+    #[test]
+    fn test_resolve_inline_only() {
+        let (style, scoped_style) = ResponsiveStyle::from([("color", "red"), ("padding", "1rem")])
+            .resolve(&Breakpoints::default());
+
+        assert_eq!(Style::from([("color", "red"), ("padding", "1rem")]), style);
+        assert_eq!(None, scoped_style);
+    }
+}
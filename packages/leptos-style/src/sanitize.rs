@@ -0,0 +1,338 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+const UNSAFE_PATTERNS: [&str; 4] = ["javascript:", "vbscript:", "expression(", "-moz-binding"];
+
+/// Error returned when a style declaration can not be emitted safely.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StyleError {
+    /// The property name contains characters that are not allowed in a CSS property name.
+    InvalidPropertyName(String),
+    /// The value contains a character which would end the declaration or block outside of a string.
+    InvalidCharacter {
+        property: String,
+        value: String,
+        character: char,
+    },
+    /// The value contains an unterminated string or unbalanced parentheses.
+    Unbalanced { property: String, value: String },
+    /// The value contains a script URL (e.g. `url(javascript:...)`) or a legacy script expression.
+    Unsafe { property: String, value: String },
+}
+
+impl Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPropertyName(property) => {
+                write!(f, "invalid property name `{property}`")
+            }
+            Self::InvalidCharacter {
+                property,
+                value,
+                character,
+            } => write!(
+                f,
+                "invalid character `{character}` in value `{value}` of property `{property}`"
+            ),
+            Self::Unbalanced { property, value } => write!(
+                f,
+                "unterminated string or unbalanced parentheses in value `{value}` of property `{property}`"
+            ),
+            Self::Unsafe { property, value } => {
+                write!(f, "unsafe value `{value}` of property `{property}`")
+            }
+        }
+    }
+}
+
+impl Error for StyleError {}
+
+/// Checks whether a property name only consists of characters allowed in a CSS property name.
+///
+/// Custom properties (`--*`) may contain any character except whitespace and CSS or HTML delimiters.
+pub fn is_valid_property_name(name: &str) -> bool {
+    if let Some(custom) = name.strip_prefix("--") {
+        return !custom.is_empty()
+            && !custom.chars().any(|char| {
+                char.is_whitespace()
+                    || matches!(char, ':' | ';' | '{' | '}' | '<' | '>' | '"' | '\'' | '\\')
+            });
+    }
+
+    let name = name.strip_prefix('-').unwrap_or(name);
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '-' | '_'))
+}
+
+/// Decodes CSS escapes, i.e. `\` followed by 1-6 hex digits and an optional whitespace, or by any other character.
+fn decode_escapes(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            decoded.push(char);
+            continue;
+        }
+
+        let mut code_point = 0;
+        let mut digits = 0;
+        while digits < 6
+            && let Some(digit) = chars.peek().and_then(|char| char.to_digit(16))
+        {
+            code_point = code_point * 16 + digit;
+            digits += 1;
+            chars.next();
+        }
+
+        if digits == 0 {
+            // An escaped newline continues the value, any other character is kept as is.
+            if let Some(next) = chars
+                .next()
+                .filter(|next| !matches!(next, '\n' | '\r' | '\u{c}'))
+            {
+                decoded.push(next);
+            }
+        } else {
+            chars.next_if(|next| next.is_whitespace());
+            decoded.push(
+                char::from_u32(code_point)
+                    .filter(|char| *char != '\0')
+                    .unwrap_or(char::REPLACEMENT_CHARACTER),
+            );
+        }
+    }
+
+    decoded
+}
+
+/// Checks whether a value contains a script URL or a legacy script expression.
+///
+/// CSS escapes are decoded and whitespace is ignored, so obfuscated variants like `\6a avascript:` are detected.
+pub fn is_unsafe_value(value: &str) -> bool {
+    let value = decode_escapes(value)
+        .chars()
+        .filter(|char| !char.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<String>();
+
+    UNSAFE_PATTERNS
+        .iter()
+        .any(|pattern| value.contains(pattern))
+}
+
+/// Sanitizes a declaration value.
+///
+/// In lenient mode characters that would end the declaration (`;`, `{` and `}`) outside of strings are escaped, in strict
+/// mode they result in an error. In both modes `<` and `>` are replaced with CSS escapes, so the value can not close an
+/// HTML element even when it is emitted without attribute escaping.
+pub fn sanitize_value(property: &str, value: &str, strict: bool) -> Result<String, StyleError> {
+    if is_unsafe_value(value) {
+        return Err(StyleError::Unsafe {
+            property: property.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    let mut sanitized = String::with_capacity(value.len());
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                sanitized.push(char);
+                match chars.next() {
+                    Some('<') => sanitized.push_str("3c "),
+                    Some('>') => sanitized.push_str("3e "),
+                    Some(next) => sanitized.push(next),
+                    None => {}
+                }
+            }
+            '<' => sanitized.push_str("\\3c "),
+            '>' => sanitized.push_str("\\3e "),
+            '\n' | '\r' | '\u{c}' if quote.is_some() => {
+                return Err(StyleError::Unbalanced {
+                    property: property.to_string(),
+                    value: value.to_string(),
+                });
+            }
+            '"' | '\'' => {
+                match quote {
+                    Some(open) if open == char => quote = None,
+                    None => quote = Some(char),
+                    _ => {}
+                }
+                sanitized.push(char);
+            }
+            '(' if quote.is_none() => {
+                depth += 1;
+                sanitized.push(char);
+            }
+            ')' if quote.is_none() => {
+                if depth == 0 {
+                    return Err(StyleError::Unbalanced {
+                        property: property.to_string(),
+                        value: value.to_string(),
+                    });
+                }
+                depth -= 1;
+                sanitized.push(char);
+            }
+            ';' | '{' | '}' if quote.is_none() => {
+                if strict {
+                    return Err(StyleError::InvalidCharacter {
+                        property: property.to_string(),
+                        value: value.to_string(),
+                        character: char,
+                    });
+                }
+                sanitized.push('\\');
+                sanitized.push(char);
+            }
+            _ => sanitized.push(char),
+        }
+    }
+
+    if quote.is_some() || depth != 0 {
+        return Err(StyleError::Unbalanced {
+            property: property.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    Ok(sanitized)
+}
+
+/// Formats a single declaration, returning `None` for empty values.
+pub(crate) fn declaration_to_string(
+    property: &str,
+    value: &str,
+    strict: bool,
+) -> Result<Option<String>, StyleError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    if !is_valid_property_name(property) {
+        return Err(StyleError::InvalidPropertyName(property.to_string()));
+    }
+
+    let value = sanitize_value(property, value, strict)?;
+    Ok(Some(format!("{property}: {value};")))
+}
+
+/// Checks a string style for script URLs and legacy script expressions.
+pub(crate) fn validate_style_string(style: &str) -> Result<(), StyleError> {
+    if is_unsafe_value(style) {
+        return Err(StyleError::Unsafe {
+            property: String::new(),
+            value: style.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_property_name() {
+        assert!(is_valid_property_name("color"));
+        assert!(is_valid_property_name("background-color"));
+        assert!(is_valid_property_name("-webkit-transform"));
+        assert!(is_valid_property_name("--main-color"));
+        assert!(is_valid_property_name("--mainColor"));
+
+        assert!(!is_valid_property_name(""));
+        assert!(!is_valid_property_name("--"));
+        assert!(!is_valid_property_name("1color"));
+        assert!(!is_valid_property_name("color; background"));
+        assert!(!is_valid_property_name("color:red"));
+        assert!(!is_valid_property_name("--x;y"));
+        assert!(!is_valid_property_name("\"><script>"));
+    }
+
+    #[test]
+    fn test_sanitize_value() {
+        assert_eq!(Ok("red".to_string()), sanitize_value("color", "red", true));
+        assert_eq!(
+            Ok("\"a;b{c}\"".to_string()),
+            sanitize_value("content", "\"a;b{c}\"", true)
+        );
+        assert_eq!(
+            Ok("url(\"image.png\")".to_string()),
+            sanitize_value("background", "url(\"image.png\")", true)
+        );
+
+        // Declaration injection
+        assert_eq!(
+            Ok("red\\; background: blue".to_string()),
+            sanitize_value("color", "red; background: blue", false)
+        );
+        assert_eq!(
+            Ok("red \\} body \\{ color: blue".to_string()),
+            sanitize_value("color", "red } body { color: blue", false)
+        );
+        assert_eq!(
+            Err(StyleError::InvalidCharacter {
+                property: "color".to_string(),
+                value: "red; background: blue".to_string(),
+                character: ';',
+            }),
+            sanitize_value("color", "red; background: blue", true)
+        );
+
+        // HTML
+        assert_eq!(
+            Ok("'\\3c /style\\3e '".to_string()),
+            sanitize_value("content", "'</style>'", true)
+        );
+
+        // Unbalanced
+        assert_eq!(
+            Err(StyleError::Unbalanced {
+                property: "content".to_string(),
+                value: "'abc".to_string(),
+            }),
+            sanitize_value("content", "'abc", false)
+        );
+        assert!(sanitize_value("background", "url(a", false).is_err());
+        assert!(sanitize_value("background", "a)", false).is_err());
+
+        // Unsafe
+        assert_eq!(
+            Err(StyleError::Unsafe {
+                property: "background".to_string(),
+                value: "url(javascript:alert(1))".to_string(),
+            }),
+            sanitize_value("background", "url(javascript:alert(1))", false)
+        );
+        assert!(sanitize_value("background", "url( 'Java\\Script:alert(1)' )", false).is_err());
+        assert!(sanitize_value("width", "expression(alert(1))", false).is_err());
+    }
+
+    #[test]
+    fn test_is_unsafe_value() {
+        assert!(!is_unsafe_value("url(image.png)"));
+        assert!(!is_unsafe_value("'\\6a'"));
+
+        assert!(is_unsafe_value("url(javascript:alert(1))"));
+        assert!(is_unsafe_value("url(\\6a avascript:alert(1))"));
+        assert!(is_unsafe_value("url(\\00006Aavascript:alert(1))"));
+        assert!(is_unsafe_value("url(java\\73 cript:alert(1))"));
+        assert!(is_unsafe_value("url(\\javascript:alert(1))"));
+        assert!(is_unsafe_value("\\65 xpression(alert(1))"));
+        assert!(is_unsafe_value("e\\78pression(alert(1))"));
+        assert!(is_unsafe_value("expr\\\nession(alert(1))"));
+        assert!(is_unsafe_value("\\2d moz-binding: url(a.xml)"));
+    }
+}
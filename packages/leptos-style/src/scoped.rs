@@ -0,0 +1,397 @@
+use std::sync::{Arc, Mutex};
+
+use indexmap::IndexMap;
+use leptos::prelude::*;
+
+use crate::{property::normalize_property_name, sanitize::declaration_to_string, style::Style};
+
+/// Identifier of the `<style>` element containing the scoped style rules.
+pub const SCOPED_STYLE_SHEET_ID: &str = "leptos-style-scoped";
+
+const CLASS_NAME_PREFIX: &str = "ls-";
+
+/// A style with nested rules, such as pseudo-classes, pseudo-elements and media queries, which is compiled into a
+/// generated class.
+///
+/// Nested selectors are resolved against the parent selector. `&` is replaced with the parent selector, selectors
+/// starting with `:` are appended to it and other selectors match descendants. Selectors starting with `@` are at-rules
+/// wrapping the nested style, e.g. `@media (min-width: 768px)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScopedStyle {
+    style: Style,
+    rules: Vec<(String, ScopedStyle)>,
+}
+
+impl ScopedStyle {
+    /// Creates a new [`ScopedStyle`] from the declarations of the generated class.
+    pub fn new<S: Into<Style>>(style: S) -> Self {
+        Self {
+            style: style.into(),
+            rules: vec![],
+        }
+    }
+
+    /// Adds a nested rule.
+    pub fn rule<S: Into<ScopedStyle>>(mut self, selector: &str, style: S) -> Self {
+        self.rules.push((selector.trim().to_string(), style.into()));
+        self
+    }
+
+    /// Returns the generated class name, which is derived from a hash of the rules.
+    pub fn class_name(&self) -> String {
+        format!(
+            "{CLASS_NAME_PREFIX}{:016x}",
+            fnv1a(self.to_css_with_selector("&").as_bytes())
+        )
+    }
+
+    /// Returns the style sheet rules for the generated class.
+    pub fn to_css(&self) -> String {
+        self.to_css_with_selector(&format!(".{}", self.class_name()))
+    }
+
+    fn to_css_with_selector(&self, selector: &str) -> String {
+        let mut rules = vec![];
+        self.write_rules(selector, &mut rules);
+        // Values are escaped already, this escapes the selectors, so no rule can close the `<style>` element.
+        rules.join("\n").replace('<', "\\3c ")
+    }
+
+    /// Formats the declarations like a structured style, so string styles are sanitized as well and declarations which
+    /// can not be emitted safely are skipped.
+    fn declarations(&self) -> String {
+        self.style
+            .iter()
+            .filter_map(|(property, value)| {
+                declaration_to_string(&normalize_property_name(property), value, false)
+                    .ok()
+                    .flatten()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn write_rules(&self, selector: &str, rules: &mut Vec<String>) {
+        let declarations = self.declarations();
+        if !declarations.is_empty() {
+            rules.push(format!("{selector} {{ {declarations} }}"));
+        }
+
+        for (nested_selector, style) in &self.rules {
+            if !is_valid_selector(nested_selector) {
+                continue;
+            }
+
+            if nested_selector.starts_with('@') {
+                let mut nested_rules = vec![];
+                style.write_rules(selector, &mut nested_rules);
+                if !nested_rules.is_empty() {
+                    rules.push(format!(
+                        "{nested_selector} {{ {} }}",
+                        nested_rules.join(" ")
+                    ));
+                }
+            } else {
+                style.write_rules(&resolve_selector(selector, nested_selector), rules);
+            }
+        }
+    }
+}
+
+impl<S: Into<Style>> From<S> for ScopedStyle {
+    fn from(value: S) -> Self {
+        ScopedStyle::new(value)
+    }
+}
+
+/// Rejects selectors which could end the rule or the `<style>` element.
+fn is_valid_selector(selector: &str) -> bool {
+    !selector.is_empty()
+        && !selector.contains(['{', '}', ';'])
+        && !selector.to_ascii_lowercase().contains("</style")
+}
+
+fn resolve_selector(parent: &str, selector: &str) -> String {
+    parent
+        .split(',')
+        .map(str::trim)
+        .flat_map(|parent| {
+            selector.split(',').map(str::trim).map(move |selector| {
+                if selector.contains('&') {
+                    selector.replace('&', parent)
+                } else if selector.starts_with(':') {
+                    format!("{parent}{selector}")
+                } else {
+                    format!("{parent} {selector}")
+                }
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Stable hash, so the server and client generate the same class names.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Debug, Default)]
+struct RegistryState {
+    rules: IndexMap<String, String>,
+    rendered: bool,
+}
+
+/// Registry collecting the rules of scoped styles for server-side rendering, deduplicated by class name.
+#[derive(Clone, Debug, Default)]
+pub struct StyleRegistry(Arc<Mutex<RegistryState>>);
+
+impl StyleRegistry {
+    /// Creates a new [`StyleRegistry`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the rules for a class, returning `false` if the class was already registered.
+    pub fn register(&self, class_name: &str, css: &str) -> bool {
+        let mut state = self.0.lock().expect("lock should not be poisoned");
+        if state.rules.contains_key(class_name) {
+            false
+        } else {
+            state.rules.insert(class_name.to_string(), css.to_string());
+            true
+        }
+    }
+
+    /// Returns `true` if the class is registered.
+    pub fn contains(&self, class_name: &str) -> bool {
+        self.0
+            .lock()
+            .expect("lock should not be poisoned")
+            .rules
+            .contains_key(class_name)
+    }
+
+    /// Returns `true` if no classes are registered.
+    pub fn is_empty(&self) -> bool {
+        self.0
+            .lock()
+            .expect("lock should not be poisoned")
+            .rules
+            .is_empty()
+    }
+
+    /// Returns `true` if the rules were rendered by a [`ScopedStyleSheet`], so rules registered afterwards are not
+    /// part of the server-rendered style sheet.
+    pub fn is_rendered(&self) -> bool {
+        self.0.lock().expect("lock should not be poisoned").rendered
+    }
+
+    /// Returns the rules of all registered classes.
+    pub fn to_css(&self) -> String {
+        self.0
+            .lock()
+            .expect("lock should not be poisoned")
+            .rules
+            .values()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the rules of all registered classes and marks the registry as rendered.
+    pub(crate) fn render(&self) -> String {
+        let css = self.to_css();
+        self.0.lock().expect("lock should not be poisoned").rendered = true;
+        css
+    }
+}
+
+/// Provides a [`StyleRegistry`] through context.
+///
+/// On the server this should be called once per request, e.g. in the root component, so the rules rendered by
+/// [`ScopedStyleSheet`] are deduplicated per request.
+pub fn provide_style_registry() -> StyleRegistry {
+    let registry = StyleRegistry::new();
+    provide_context(registry.clone());
+    registry
+}
+
+/// Registers a scoped style and returns its generated class name.
+///
+/// During server-side rendering the rules are collected in the [`StyleRegistry`] from context. In the browser they are
+/// injected into the `<style>` element with id [`SCOPED_STYLE_SHEET_ID`] in `<head>`, which is created if needed.
+pub fn use_scoped_style<S: Into<ScopedStyle>>(style: S) -> String {
+    let style: ScopedStyle = style.into();
+    let class_name = style.class_name();
+    let css = style.to_css();
+
+    if let Some(registry) = use_context::<StyleRegistry>() {
+        registry.register(&class_name, &css);
+    }
+
+    Effect::new({
+        let class_name = class_name.clone();
+        move |_| inject_rules(&class_name, &css)
+    });
+
+    class_name
+}
+
+fn inject_rules(class_name: &str, css: &str) {
+    let document = document();
+    let Some(head) = document.head() else {
+        return;
+    };
+
+    let style_sheet = match document.get_element_by_id(SCOPED_STYLE_SHEET_ID) {
+        Some(style_sheet) => style_sheet,
+        None => {
+            let Ok(style_sheet) = document.create_element("style") else {
+                return;
+            };
+            style_sheet.set_id(SCOPED_STYLE_SHEET_ID);
+            if head.append_child(&style_sheet).is_err() {
+                return;
+            }
+            style_sheet
+        }
+    };
+
+    let selector = format!(".{class_name}");
+    if style_sheet
+        .text_content()
+        .is_some_and(|text| text.contains(&selector))
+    {
+        return;
+    }
+
+    let text = document.create_text_node(&format!("\n{css}"));
+    _ = style_sheet.append_child(&text);
+}
+
+/// Renders the rules collected in the [`StyleRegistry`] from context into a `<style>` element.
+///
+/// The rules are read when the element is rendered, so it should be placed after the content using scoped styles, e.g.
+/// at the end of the root component. Content rendered afterwards, such as streamed [`Suspense`] chunks, can not add
+/// rules to the server-rendered style sheet.
+#[component]
+pub fn ScopedStyleSheet() -> impl IntoView {
+    let registry = use_context::<StyleRegistry>().unwrap_or_default();
+
+    view! {
+        <style id=SCOPED_STYLE_SHEET_ID inner_html=move || registry.render()></style>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_name() {
+        let style = ScopedStyle::new([("color", "red")]);
+        assert!(style.class_name().starts_with(CLASS_NAME_PREFIX));
+        assert_eq!(
+            style.class_name(),
+            ScopedStyle::new([("color", "red")]).class_name()
+        );
+        assert_ne!(
+            style.class_name(),
+            ScopedStyle::new([("color", "blue")]).class_name()
+        );
+        assert_ne!(
+            style.class_name(),
+            ScopedStyle::new([("color", "red")])
+                .rule("&:hover", [("color", "blue")])
+                .class_name()
+        );
+    }
+
+    #[test]
+    fn test_to_css() {
+        let style = ScopedStyle::new([("color", "red")])
+            .rule("&:hover, &:focus", [("color", "blue")])
+            .rule(":disabled", [("opacity", "0.5")])
+            .rule("> span", [("padding", "0.5rem")])
+            .rule("&::before", [("content", "'*'")])
+            .rule(
+                "@media (min-width: 768px)",
+                ScopedStyle::new([("color", "green")]).rule(":hover", [("color", "yellow")]),
+            )
+            .rule("} body {", [("display", "none")]);
+        let class_name = style.class_name();
+
+        assert_eq!(
+            [
+                format!(".{class_name} {{ color: red; }}"),
+                format!(".{class_name}:hover, .{class_name}:focus {{ color: blue; }}"),
+                format!(".{class_name}:disabled {{ opacity: 0.5; }}"),
+                format!(".{class_name} > span {{ padding: 0.5rem; }}"),
+                format!(".{class_name}::before {{ content: '*'; }}"),
+                format!(
+                    "@media (min-width: 768px) {{ .{class_name} {{ color: green; }} .{class_name}:hover {{ color: yellow; }} }}"
+                ),
+            ]
+            .join("\n"),
+            style.to_css()
+        );
+    }
+
+    #[test]
+    fn test_to_css_sanitized() {
+        let style = ScopedStyle::new(
+            "color: red</style><script>alert(1)</script>; margin: 0; margin: 1px;",
+        )
+        .rule(
+            "&[data-x=\"<\"]",
+            "background: url(javascript:alert(1)); padding: 0 }",
+        );
+        let css = style.to_css();
+        let class_name = style.class_name();
+
+        assert!(!css.contains('<'));
+        assert_eq!(
+            [
+                format!(
+                    ".{class_name} {{ color: red\\3c /style\\3e \\3c script\\3e alert(1)\\3c /script\\3e ; margin: 0; margin: 1px; }}"
+                ),
+                format!(".{class_name}[data-x=\"\\3c \"] {{ padding: 0 \\}}; }}"),
+            ]
+            .join("\n"),
+            css
+        );
+    }
+
+    #[test]
+    fn test_resolve_selector() {
+        assert_eq!(".a:hover", resolve_selector(".a", "&:hover"));
+        assert_eq!(".b .a", resolve_selector(".a", ".b &"));
+        assert_eq!(".a .b", resolve_selector(".a", ".b"));
+        assert_eq!(
+            ".a:hover, .a:focus, .b:hover, .b:focus",
+            resolve_selector(".a, .b", ":hover, :focus")
+        );
+    }
+
+    #[test]
+    fn test_style_registry() {
+        let registry = StyleRegistry::new();
+        assert!(registry.is_empty());
+
+        assert!(registry.register("ls-a", ".ls-a { color: red; }"));
+        assert!(!registry.register("ls-a", ".ls-a { color: red; }"));
+        assert!(registry.register("ls-b", ".ls-b { color: blue; }"));
+        assert!(registry.contains("ls-a"));
+        assert!(!registry.contains("ls-c"));
+        assert_eq!(
+            ".ls-a { color: red; }\n.ls-b { color: blue; }",
+            registry.to_css()
+        );
+
+        assert!(!registry.is_rendered());
+        assert_eq!(registry.to_css(), registry.render());
+        assert!(registry.is_rendered());
+    }
+}
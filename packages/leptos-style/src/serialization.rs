@@ -0,0 +1,131 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, EnumAccess, MapAccess, VariantAccess, Visitor},
+};
+
+use crate::style::{InnerStyle, Style};
+
+const VARIANTS: &[&str] = &["String", "Structured"];
+
+/// Serializes string styles as a string and structured styles as an ordered object.
+///
+/// Formats which are not human readable (e.g. postcard or bincode) can not tell strings and objects apart without a
+/// tag, so the style is serialized as an enum instead.
+impl Serialize for InnerStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::String(string) if serializer.is_human_readable() => {
+                serializer.serialize_str(string)
+            }
+            Self::String(string) => {
+                serializer.serialize_newtype_variant("InnerStyle", 0, VARIANTS[0], string)
+            }
+            Self::Structured(map) if serializer.is_human_readable() => map.serialize(serializer),
+            Self::Structured(map) => {
+                serializer.serialize_newtype_variant("InnerStyle", 1, VARIANTS[1], map)
+            }
+        }
+    }
+}
+
+enum Variant {
+    String,
+    Structured,
+}
+
+struct VariantVisitor;
+
+impl Visitor<'_> for VariantVisitor {
+    type Value = Variant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a style variant")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        match value {
+            0 => Ok(Variant::String),
+            1 => Ok(Variant::Structured),
+            _ => Err(E::invalid_value(
+                de::Unexpected::Unsigned(value),
+                &"variant index 0 <= i < 2",
+            )),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        match value {
+            "String" => Ok(Variant::String),
+            "Structured" => Ok(Variant::Structured),
+            _ => Err(E::unknown_variant(value, VARIANTS)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+struct InnerStyleVisitor;
+
+impl<'de> Visitor<'de> for InnerStyleVisitor {
+    type Value = InnerStyle;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a style string or a map of properties to optional values")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(InnerStyle::String(value.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(InnerStyle::String(value))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = IndexMap::with_capacity(access.size_hint().unwrap_or_default());
+        while let Some((property, value)) = access.next_entry::<String, Option<String>>()? {
+            map.insert(property, value);
+        }
+
+        Ok(InnerStyle::structured(map))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, access: A) -> Result<Self::Value, A::Error> {
+        match access.variant()? {
+            (Variant::String, access) => access.newtype_variant().map(InnerStyle::String),
+            (Variant::Structured, access) => access
+                .newtype_variant::<IndexMap<String, Option<String>>>()
+                .map(InnerStyle::structured),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InnerStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(InnerStyleVisitor)
+        } else {
+            deserializer.deserialize_enum("InnerStyle", VARIANTS, InnerStyleVisitor)
+        }
+    }
+}
+
+/// Serializes an empty style as `null`, otherwise as the [`InnerStyle`].
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<InnerStyle>::deserialize(deserializer).map(Style)
+    }
+}
@@ -0,0 +1,259 @@
+use indexmap::IndexMap;
+
+/// Returns the properties directly set by a shorthand property, which may be shorthands themselves.
+///
+/// Covers the margin, padding, border, inset, flex, grid, font and background families.
+pub fn shorthand_properties(shorthand: &str) -> &'static [&'static str] {
+    match shorthand {
+        "margin" => &[
+            "margin-top",
+            "margin-right",
+            "margin-bottom",
+            "margin-left",
+            "margin-block",
+            "margin-inline",
+        ],
+        "margin-block" => &["margin-block-start", "margin-block-end"],
+        "margin-inline" => &["margin-inline-start", "margin-inline-end"],
+        "padding" => &[
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+            "padding-block",
+            "padding-inline",
+        ],
+        "padding-block" => &["padding-block-start", "padding-block-end"],
+        "padding-inline" => &["padding-inline-start", "padding-inline-end"],
+        "inset" => &[
+            "top",
+            "right",
+            "bottom",
+            "left",
+            "inset-block",
+            "inset-inline",
+        ],
+        "inset-block" => &["inset-block-start", "inset-block-end"],
+        "inset-inline" => &["inset-inline-start", "inset-inline-end"],
+        "border" => &[
+            "border-top",
+            "border-right",
+            "border-bottom",
+            "border-left",
+            "border-width",
+            "border-style",
+            "border-color",
+            "border-block",
+            "border-inline",
+            "border-image",
+        ],
+        "border-top" => &["border-top-width", "border-top-style", "border-top-color"],
+        "border-right" => &[
+            "border-right-width",
+            "border-right-style",
+            "border-right-color",
+        ],
+        "border-bottom" => &[
+            "border-bottom-width",
+            "border-bottom-style",
+            "border-bottom-color",
+        ],
+        "border-left" => &[
+            "border-left-width",
+            "border-left-style",
+            "border-left-color",
+        ],
+        "border-width" => &[
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+        ],
+        "border-style" => &[
+            "border-top-style",
+            "border-right-style",
+            "border-bottom-style",
+            "border-left-style",
+        ],
+        "border-color" => &[
+            "border-top-color",
+            "border-right-color",
+            "border-bottom-color",
+            "border-left-color",
+        ],
+        "border-block" => &[
+            "border-block-start",
+            "border-block-end",
+            "border-block-width",
+            "border-block-style",
+            "border-block-color",
+        ],
+        "border-inline" => &[
+            "border-inline-start",
+            "border-inline-end",
+            "border-inline-width",
+            "border-inline-style",
+            "border-inline-color",
+        ],
+        "border-image" => &[
+            "border-image-source",
+            "border-image-slice",
+            "border-image-width",
+            "border-image-outset",
+            "border-image-repeat",
+        ],
+        "border-radius" => &[
+            "border-top-left-radius",
+            "border-top-right-radius",
+            "border-bottom-right-radius",
+            "border-bottom-left-radius",
+        ],
+        "flex" => &["flex-grow", "flex-shrink", "flex-basis"],
+        "flex-flow" => &["flex-direction", "flex-wrap"],
+        "grid" => &[
+            "grid-template",
+            "grid-auto-rows",
+            "grid-auto-columns",
+            "grid-auto-flow",
+        ],
+        "grid-template" => &[
+            "grid-template-rows",
+            "grid-template-columns",
+            "grid-template-areas",
+        ],
+        "grid-area" => &["grid-row", "grid-column"],
+        "grid-row" => &["grid-row-start", "grid-row-end"],
+        "grid-column" => &["grid-column-start", "grid-column-end"],
+        "gap" => &["row-gap", "column-gap"],
+        "font" => &[
+            "font-style",
+            "font-variant",
+            "font-weight",
+            "font-stretch",
+            "font-size",
+            "line-height",
+            "font-family",
+        ],
+        "background" => &[
+            "background-color",
+            "background-image",
+            "background-position",
+            "background-size",
+            "background-repeat",
+            "background-origin",
+            "background-clip",
+            "background-attachment",
+        ],
+        "background-position" => &["background-position-x", "background-position-y"],
+        _ => &[],
+    }
+}
+
+/// Returns `true` if the property is set by the shorthand, directly or through a nested shorthand.
+pub fn is_longhand_of(property: &str, shorthand: &str) -> bool {
+    shorthand_properties(shorthand)
+        .iter()
+        .any(|sub_property| *sub_property == property || is_longhand_of(property, sub_property))
+}
+
+/// Merges structured styles. See [`InnerStyle::with_defaults`](crate::InnerStyle::with_defaults) for the precedence rules.
+pub(crate) fn merge_style_maps(
+    map: IndexMap<String, Option<String>>,
+    mut defaults: IndexMap<String, Option<String>>,
+) -> IndexMap<String, Option<String>> {
+    for property in map.keys() {
+        defaults.retain(|default_property, _| {
+            default_property != property && !is_longhand_of(default_property, property)
+        });
+    }
+
+    defaults.extend(map);
+    defaults
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_longhand_of() {
+        assert!(is_longhand_of("margin-top", "margin"));
+        assert!(is_longhand_of("margin-block-start", "margin"));
+        assert!(is_longhand_of("top", "inset"));
+        assert!(is_longhand_of("border-top-width", "border"));
+        assert!(is_longhand_of("border-top-width", "border-top"));
+        assert!(is_longhand_of("border-top-width", "border-width"));
+        assert!(is_longhand_of("border-image-source", "border"));
+        assert!(is_longhand_of("flex-basis", "flex"));
+        assert!(is_longhand_of("grid-template-areas", "grid"));
+        assert!(is_longhand_of("line-height", "font"));
+        assert!(is_longhand_of("background-position-x", "background"));
+
+        assert!(!is_longhand_of("margin", "margin"));
+        assert!(!is_longhand_of("margin", "margin-top"));
+        assert!(!is_longhand_of("border-radius", "border"));
+        assert!(!is_longhand_of("border-top-width", "border-bottom"));
+        assert!(!is_longhand_of("flex-direction", "flex"));
+        assert!(!is_longhand_of("color", "background"));
+    }
+
+    #[test]
+    fn test_merge_style_maps() {
+        fn map<const N: usize>(
+            declarations: [(&str, Option<&str>); N],
+        ) -> IndexMap<String, Option<String>> {
+            declarations
+                .into_iter()
+                .map(|(property, value)| (property.to_string(), value.map(str::to_string)))
+                .collect()
+        }
+
+        // Shorthand replaces default longhands.
+        assert_eq!(
+            map([("color", Some("red")), ("margin", Some("1rem"))]),
+            merge_style_maps(
+                map([("margin", Some("1rem"))]),
+                map([("margin-top", Some("0")), ("color", Some("red"))]),
+            )
+        );
+        assert_eq!(
+            map([("margin", None)]),
+            merge_style_maps(
+                map([("margin", None)]),
+                map([("margin", Some("0")), ("margin-block-start", Some("0"))]),
+            )
+        );
+
+        // Longhand is placed after default shorthand.
+        assert_eq!(
+            map([("margin", Some("0")), ("margin-top", Some("1rem"))]),
+            merge_style_maps(
+                map([("margin-top", Some("1rem"))]),
+                map([("margin-top", Some("2rem")), ("margin", Some("0"))]),
+            )
+        );
+        assert_eq!(
+            map([
+                ("border", Some("1px solid black")),
+                ("border-top-color", Some("red"))
+            ]),
+            merge_style_maps(
+                map([("border-top-color", Some("red"))]),
+                map([("border", Some("1px solid black"))]),
+            )
+        );
+
+        // Unrelated properties are kept in order.
+        assert_eq!(
+            map([
+                ("padding", Some("0.5rem")),
+                ("color", Some("blue")),
+                ("display", Some("flex"))
+            ]),
+            merge_style_maps(
+                map([("color", Some("blue")), ("display", Some("flex"))]),
+                map([("padding", Some("0.5rem")), ("color", Some("red"))]),
+            )
+        );
+    }
+}
@@ -3,20 +3,50 @@ use std::{
     ops::Deref,
 };
 
-use indexmap::IndexMap;
-use leptos::{attr::IntoAttributeValue, tachys::html::style::IntoStyle};
+use indexmap::{IndexMap, IndexSet};
+use leptos::{
+    attr::IntoAttributeValue,
+    prelude::use_context,
+    tachys::{html::style::IntoStyle, renderer::Rndr},
+};
+
+use crate::{
+    dedupe::{StyleDedupe, hoisted_style_markers},
+    parse::{ParseError, normalize_value, parse_declarations, split_declarations},
+    property::{
+        VendorPrefix, expand_vendor_prefixes, normalize_property_name, normalize_style_map,
+    },
+    sanitize::{
+        StyleError, declaration_to_string, is_unsafe_value, is_valid_property_name,
+        validate_style_string,
+    },
+    shorthand::merge_style_maps,
+};
 
+/// Formats the declarations, skipping declarations which can not be emitted safely.
 fn style_map_to_string(map: &IndexMap<String, Option<String>>) -> String {
     map.iter()
         .filter_map(|(key, value)| {
             value
                 .as_ref()
-                .and_then(|value| (!value.is_empty()).then_some(format!("{key}: {value};")))
+                .and_then(|value| declaration_to_string(key, value, false).ok().flatten())
         })
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+fn style_map_try_to_string(map: &IndexMap<String, Option<String>>) -> Result<String, StyleError> {
+    Ok(map
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .and_then(|value| declaration_to_string(key, value, true).transpose())
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join(" "))
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum InnerStyle {
     String(String),
@@ -24,6 +54,120 @@ pub enum InnerStyle {
 }
 
 impl InnerStyle {
+    /// Creates a structured style, normalizing property names with [`normalize_property_name`].
+    pub fn structured<K: AsRef<str>>(iter: impl IntoIterator<Item = (K, Option<String>)>) -> Self {
+        Self::Structured(normalize_style_map(
+            iter.into_iter()
+                .map(|(key, value)| (key.as_ref().to_string(), value))
+                .collect(),
+        ))
+    }
+
+    /// Converts a string style into a structured style by parsing its declarations.
+    pub fn into_structured(self) -> Self {
+        match self {
+            Self::String(string) => Self::structured(
+                split_declarations(&string)
+                    .into_iter()
+                    .map(|(property, value)| (property, Some(value.to_string()))),
+            ),
+            Self::Structured(map) => Self::Structured(map),
+        }
+    }
+
+    fn map_mut(&mut self) -> &mut IndexMap<String, Option<String>> {
+        if let Self::String(string) = self {
+            *self = Self::String(std::mem::take(string)).into_structured();
+        }
+
+        match self {
+            Self::Structured(map) => map,
+            Self::String(_) => unreachable!("style should be structured"),
+        }
+    }
+
+    /// Iterates over the declarations with a non-empty value.
+    ///
+    /// Property names of string styles are returned as written.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        match self {
+            Self::String(string) => split_declarations(string),
+            Self::Structured(map) => map
+                .iter()
+                .filter_map(|(property, value)| {
+                    value
+                        .as_deref()
+                        .filter(|value| !value.is_empty())
+                        .map(|value| (property.as_str(), value))
+                })
+                .collect(),
+        }
+        .into_iter()
+    }
+
+    /// Returns the value of the last declaration of the property.
+    pub fn get(&self, property: &str) -> Option<&str> {
+        let property = normalize_property_name(property);
+
+        self.iter()
+            .filter(|(key, _)| normalize_property_name(key) == property)
+            .last()
+            .map(|(_, value)| value)
+    }
+
+    /// Returns `true` if the style has a declaration with a non-empty value for the property.
+    pub fn contains(&self, property: &str) -> bool {
+        self.get(property).is_some()
+    }
+
+    /// Sets the value of the property, returning the previous value.
+    ///
+    /// String styles are converted into structured styles first.
+    pub fn insert<K: AsRef<str>, V: Into<String>>(
+        &mut self,
+        property: K,
+        value: V,
+    ) -> Option<String> {
+        self.map_mut()
+            .insert(
+                normalize_property_name(property.as_ref()),
+                Some(value.into()),
+            )
+            .flatten()
+    }
+
+    /// Removes the property, returning its value.
+    ///
+    /// String styles are converted into structured styles first.
+    pub fn remove(&mut self, property: &str) -> Option<String> {
+        self.map_mut()
+            .shift_remove(&normalize_property_name(property))
+            .flatten()
+    }
+
+    /// Retains only the declarations for which the predicate returns `true`.
+    ///
+    /// The predicate is called with the property name and value. Properties without a value are always retained, so they
+    /// still override defaults. String styles are converted into structured styles first.
+    pub fn retain<F: FnMut(&str, &str) -> bool>(&mut self, mut f: F) {
+        self.map_mut().retain(|property, value| {
+            value
+                .as_deref()
+                .is_none_or(|value| value.is_empty() || f(property, value))
+        });
+    }
+
+    /// Merges the style with defaults, with declarations of the style taking precedence.
+    ///
+    /// If either style is a string, the defaults are prepended, so later declarations win as usual in CSS. Structured
+    /// styles are merged with awareness of shorthand properties (see [`shorthand_properties`](crate::shorthand_properties)):
+    ///
+    /// - A declaration replaces the default declaration of the same property.
+    /// - A shorthand replaces the default declarations of its longhands, e.g. `margin` replaces a default `margin-top`.
+    /// - A longhand is placed after the default declaration of its shorthand, so it overrides that part of the
+    ///   shorthand, e.g. `margin-top` overrides the top margin of a default `margin`.
+    ///
+    /// Unset declarations (`None`) follow the same rules, so they also remove default longhands.
     pub fn with_defaults<I: Into<InnerStyle>>(self, defaults: I) -> Self {
         let defaults: InnerStyle = defaults.into();
 
@@ -37,13 +181,67 @@ impl InnerStyle {
             (Self::Structured(map), Self::String(default_string)) => {
                 Self::String(format!("{} {}", default_string, style_map_to_string(&map)))
             }
-            (Self::Structured(map), Self::Structured(default_map)) => {
-                InnerStyle::Structured(default_map.into_iter().chain(map).collect())
+            (Self::Structured(map), Self::Structured(default_map)) => InnerStyle::Structured(
+                merge_style_maps(normalize_style_map(map), normalize_style_map(default_map)),
+            ),
+        }
+    }
+
+    /// Formats the style, returning an error instead of skipping or escaping unsafe declarations.
+    ///
+    /// String styles are emitted as written, but are still rejected if they contain a script URL.
+    pub fn try_to_string(&self) -> Result<String, StyleError> {
+        match self {
+            Self::String(string) => {
+                validate_style_string(string)?;
+                Ok(string.clone())
+            }
+            Self::Structured(map) => style_map_try_to_string(map),
+        }
+    }
+
+    /// Adds vendor-prefixed variants of the given properties before their unprefixed declaration.
+    ///
+    /// Only structured styles are expanded, string styles are returned as is.
+    pub fn with_vendor_prefixes<K, P>(self, properties: impl IntoIterator<Item = (K, P)>) -> Self
+    where
+        K: AsRef<str>,
+        P: AsRef<[VendorPrefix]>,
+    {
+        match self {
+            Self::String(string) => Self::String(string),
+            Self::Structured(map) => {
+                let properties = properties
+                    .into_iter()
+                    .map(|(key, prefixes)| {
+                        (
+                            crate::normalize_property_name(key.as_ref()),
+                            prefixes.as_ref().to_vec(),
+                        )
+                    })
+                    .collect();
+
+                Self::Structured(expand_vendor_prefixes(
+                    normalize_style_map(map),
+                    &properties,
+                ))
             }
         }
     }
 }
 
+impl<K: AsRef<str>, V: Into<String>> Extend<(K, V)> for InnerStyle {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let map = self.map_mut();
+        for (property, value) in iter {
+            map.insert(
+                normalize_property_name(property.as_ref()),
+                Some(value.into()),
+            );
+        }
+    }
+}
+
 impl Display for InnerStyle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -61,6 +259,7 @@ impl Style {
         Self::default()
     }
 
+    /// Merges the style with defaults. See [`InnerStyle::with_defaults`].
     pub fn with_defaults<I: Into<Self>>(self, defaults: I) -> Self {
         let defaults: Self = defaults.into();
 
@@ -71,6 +270,105 @@ impl Style {
             (None, None) => None,
         })
     }
+
+    /// Parses a style string into a structured style.
+    ///
+    /// Unlike `From<&str>`, which keeps the string as is, malformed declarations are reported with their position.
+    pub fn parse(style: &str) -> Result<Self, ParseError> {
+        let declarations = parse_declarations(style)?;
+
+        Ok(if declarations.is_empty() {
+            Style::default()
+        } else {
+            Style(Some(InnerStyle::structured(declarations.into_iter().map(
+                |(property, value)| (property, Some(value.to_string())),
+            ))))
+        })
+    }
+
+    /// Reads the `style` attribute of an element into a structured style.
+    ///
+    /// Malformed declarations are skipped, see [`Style::parse`] for strict parsing.
+    pub fn from_element(el: &leptos::tachys::renderer::types::Element) -> Self {
+        let style = el.get_attribute("style").unwrap_or_default();
+        if split_declarations(&style).is_empty() {
+            Style::default()
+        } else {
+            Style(Some(InnerStyle::String(style).into_structured()))
+        }
+    }
+
+    /// Returns `true` if both styles have the same declarations.
+    ///
+    /// Ordering, whitespace and string or structured representation are ignored. Property names are normalized and
+    /// later declarations of a property override earlier ones.
+    pub fn is_equivalent(&self, other: &Style) -> bool {
+        semantic_declarations(self) == semantic_declarations(other)
+    }
+
+    fn inner_mut(&mut self) -> &mut InnerStyle {
+        self.0
+            .get_or_insert_with(|| InnerStyle::Structured(IndexMap::new()))
+    }
+
+    /// Iterates over the declarations. See [`InnerStyle::iter`].
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().flat_map(InnerStyle::iter)
+    }
+
+    /// Returns the value of the last declaration of the property.
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.0.as_ref().and_then(|style| style.get(property))
+    }
+
+    /// Returns `true` if the style has a declaration with a non-empty value for the property.
+    pub fn contains(&self, property: &str) -> bool {
+        self.get(property).is_some()
+    }
+
+    /// Sets the value of the property, returning the previous value. See [`InnerStyle::insert`].
+    pub fn insert<K: AsRef<str>, V: Into<String>>(
+        &mut self,
+        property: K,
+        value: V,
+    ) -> Option<String> {
+        self.inner_mut().insert(property, value)
+    }
+
+    /// Removes the property, returning its value. See [`InnerStyle::remove`].
+    pub fn remove(&mut self, property: &str) -> Option<String> {
+        self.0.as_mut().and_then(|style| style.remove(property))
+    }
+
+    /// Retains only the declarations for which the predicate returns `true`. See [`InnerStyle::retain`].
+    pub fn retain<F: FnMut(&str, &str) -> bool>(&mut self, f: F) {
+        if let Some(style) = self.0.as_mut() {
+            style.retain(f);
+        }
+    }
+
+    /// Formats the style in strict mode. See [`InnerStyle::try_to_string`].
+    pub fn try_to_string(&self) -> Result<String, StyleError> {
+        self.0
+            .as_ref()
+            .map(InnerStyle::try_to_string)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Checks that the style can be emitted without escaping or skipping declarations.
+    pub fn validate(&self) -> Result<(), StyleError> {
+        self.try_to_string().map(|_| ())
+    }
+
+    /// Adds vendor-prefixed variants of the given properties. See [`InnerStyle::with_vendor_prefixes`].
+    pub fn with_vendor_prefixes<K, P>(self, properties: impl IntoIterator<Item = (K, P)>) -> Self
+    where
+        K: AsRef<str>,
+        P: AsRef<[VendorPrefix]>,
+    {
+        Style(self.0.map(|style| style.with_vendor_prefixes(properties)))
+    }
 }
 
 impl Deref for Style {
@@ -81,6 +379,12 @@ impl Deref for Style {
     }
 }
 
+impl<K: AsRef<str>, V: Into<String>> Extend<(K, V)> for Style {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        self.inner_mut().extend(iter);
+    }
+}
+
 impl Display for Style {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -120,64 +424,59 @@ impl From<String> for Style {
 
 impl From<IndexMap<String, Option<String>>> for Style {
     fn from(value: IndexMap<String, Option<String>>) -> Style {
-        Style(Some(InnerStyle::Structured(value)))
+        Style(Some(InnerStyle::structured(value)))
     }
 }
 
 impl From<IndexMap<String, String>> for Style {
     fn from(value: IndexMap<String, String>) -> Style {
-        Style(Some(InnerStyle::Structured(
-            value
-                .into_iter()
-                .map(|(key, value)| (key, Some(value)))
-                .collect(),
+        Style(Some(InnerStyle::structured(
+            value.into_iter().map(|(key, value)| (key, Some(value))),
         )))
     }
 }
 
 impl<const N: usize> From<[(&str, Option<&str>); N]> for Style {
     fn from(value: [(&str, Option<&str>); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(
-            value.map(|(key, value)| (key.to_string(), value.map(|value| value.to_string()))),
-        ))))
+        Style(Some(InnerStyle::structured(value.map(|(key, value)| {
+            (key, value.map(|value| value.to_string()))
+        }))))
     }
 }
 
 impl<const N: usize> From<[(&str, &str); N]> for Style {
     fn from(value: [(&str, &str); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(
-            value.map(|(key, value)| (key.to_string(), Some(value.to_string()))),
-        ))))
+        Style(Some(InnerStyle::structured(
+            value.map(|(key, value)| (key, Some(value.to_string()))),
+        )))
     }
 }
 
 impl<const N: usize> From<[(&str, Option<String>); N]> for Style {
     fn from(value: [(&str, Option<String>); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(
-            value.map(|(key, value)| (key.to_string(), value)),
-        ))))
+        Style(Some(InnerStyle::structured(value)))
     }
 }
 
 impl<const N: usize> From<[(&str, String); N]> for Style {
     fn from(value: [(&str, String); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(
-            value.map(|(key, value)| (key.to_string(), Some(value))),
-        ))))
+        Style(Some(InnerStyle::structured(
+            value.map(|(key, value)| (key, Some(value))),
+        )))
     }
 }
 
 impl<const N: usize> From<[(String, Option<String>); N]> for Style {
     fn from(value: [(String, Option<String>); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(value))))
+        Style(Some(InnerStyle::structured(value)))
     }
 }
 
 impl<const N: usize> From<[(String, String); N]> for Style {
     fn from(value: [(String, String); N]) -> Style {
-        Style(Some(InnerStyle::Structured(IndexMap::from_iter(
+        Style(Some(InnerStyle::structured(
             value.map(|(key, value)| (key, Some(value))),
-        ))))
+        )))
     }
 }
 
@@ -189,34 +488,175 @@ impl IntoAttributeValue for Style {
     }
 }
 
+fn semantic_declarations(style: &Style) -> IndexMap<String, String> {
+    style
+        .iter()
+        .map(|(property, value)| (normalize_property_name(property), normalize_value(value)))
+        .collect()
+}
+
+/// Declarations applied to an element with normalized property names, in source order including duplicates.
+pub(crate) type AppliedDeclarations = Vec<(String, String)>;
+
+fn applied_declarations(style: &Style) -> AppliedDeclarations {
+    style
+        .iter()
+        .map(|(property, value)| (normalize_property_name(property), value))
+        .filter(|(property, value)| is_valid_property_name(property) && !is_unsafe_value(value))
+        .map(|(property, value)| (property, value.to_string()))
+        .collect()
+}
+
+/// Splits an `!important` priority from a value, as `setProperty` expects the priority as a separate argument.
+fn split_priority(value: &str) -> (&str, Option<&'static str>) {
+    let trimmed = value.trim_end();
+    if let Some(index) = trimmed.rfind('!')
+        && trimmed[index + 1..]
+            .trim()
+            .eq_ignore_ascii_case("important")
+    {
+        return (trimmed[..index].trim_end(), Some("important"));
+    }
+
+    (value, None)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DeclarationUpdate<'a> {
+    Remove(&'a str),
+    Set(&'a str, &'a str),
+}
+
+/// Returns the updates turning the previously applied declarations into the next ones.
+///
+/// Properties which are no longer declared are removed first. Then all declarations are set again in order, as setting
+/// or removing a shorthand also changes its longhands, and later declarations must keep overriding earlier ones.
+fn declaration_updates<'a>(
+    prev: &'a AppliedDeclarations,
+    next: &'a AppliedDeclarations,
+) -> Vec<DeclarationUpdate<'a>> {
+    if prev == next {
+        return vec![];
+    }
+
+    let mut removed = IndexSet::new();
+    for (property, _) in prev {
+        if !next
+            .iter()
+            .any(|(next_property, _)| next_property == property)
+        {
+            removed.insert(property.as_str());
+        }
+    }
+
+    removed
+        .into_iter()
+        .map(DeclarationUpdate::Remove)
+        .chain(
+            next.iter()
+                .map(|(property, value)| DeclarationUpdate::Set(property, value)),
+        )
+        .collect()
+}
+
+pub(crate) fn apply_declarations(
+    el: &leptos::tachys::renderer::types::Element,
+    prev: &AppliedDeclarations,
+    next: &AppliedDeclarations,
+) {
+    let updates = declaration_updates(prev, next);
+    if updates.is_empty() {
+        return;
+    }
+
+    let style = Rndr::style(el);
+
+    for update in updates {
+        match update {
+            DeclarationUpdate::Remove(property) => Rndr::remove_css_property(&style, property),
+            DeclarationUpdate::Set(property, value) => match split_priority(value) {
+                (value, Some(priority)) => {
+                    _ = style.set_property_with_priority(property, value, priority);
+                }
+                (value, None) => Rndr::set_css_property(&style, property, value),
+            },
+        }
+    }
+}
+
+/// Applies the declarations individually with `setProperty` instead of overwriting the `style` attribute.
+///
+/// Only the properties set by this style are changed on rebuild and removed on reset, so a [`Style`] can be combined with
+/// `style:prop=` directives on the same element.
+///
+/// In debug builds, hydration compares the server-rendered style with the client style. See [`StyleHydrationOptions`](crate::StyleHydrationOptions).
 impl IntoStyle for Style {
     type AsyncOutput = Self;
-    type State = (leptos::tachys::renderer::types::Element, Self);
+    type State = (
+        leptos::tachys::renderer::types::Element,
+        AppliedDeclarations,
+    );
     type Cloneable = Self;
     type CloneableOwned = Self;
 
     fn to_html(self, style: &mut String) {
-        style.push_str(&self.to_string());
+        let declarations = self.to_string();
+
+        if matches!(self.0, Some(InnerStyle::Structured(_)))
+            && let Some(marker) =
+                use_context::<StyleDedupe>().and_then(|dedupe| dedupe.hoist(&declarations))
+        {
+            style.push_str(&marker);
+        } else {
+            style.push_str(&declarations);
+        }
+
+        // Separate the declarations from those of `style:` directives, which are appended directly.
+        let style_end = style.trim_end();
+        if !style_end.is_empty() && !style_end.ends_with(';') {
+            style.push(';');
+        }
     }
 
     fn hydrate<const FROM_SERVER: bool>(
         self,
         el: &leptos::tachys::renderer::types::Element,
     ) -> Self::State {
-        (el.clone(), self)
+        let declarations = applied_declarations(&self);
+
+        if FROM_SERVER {
+            // Replace the marker of a style hoisted by `StyleDedupe` with the inline style.
+            let server = el.get_attribute("style").unwrap_or_default();
+            let markers = hoisted_style_markers(&server);
+            if !markers.is_empty() {
+                apply_declarations(el, &AppliedDeclarations::new(), &declarations);
+                let style = Rndr::style(el);
+                for marker in markers {
+                    Rndr::remove_css_property(&style, marker);
+                }
+                return (el.clone(), declarations);
+            }
+
+            #[cfg(debug_assertions)]
+            crate::hydration::validate_hydration(el, &self, &declarations);
+        }
+
+        (el.clone(), declarations)
     }
 
     fn build(self, el: &leptos::tachys::renderer::types::Element) -> Self::State {
-        leptos::tachys::renderer::Rndr::set_attribute(el, "style", &self.to_string());
-        (el.clone(), self)
+        let declarations = applied_declarations(&self);
+        apply_declarations(el, &AppliedDeclarations::new(), &declarations);
+        (el.clone(), declarations)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
-        if self != *prev {
-            leptos::tachys::renderer::Rndr::set_attribute(el, "style", &self.to_string());
+        let declarations = applied_declarations(&self);
+        if declarations != *prev {
+            apply_declarations(el, prev, &declarations);
         }
-        *prev = self;
+        *prev = declarations;
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -234,8 +674,9 @@ impl IntoStyle for Style {
     }
 
     fn reset(state: &mut Self::State) {
-        let (el, _prev) = state;
-        leptos::tachys::renderer::Rndr::remove_attribute(el, "style");
+        let (el, prev) = state;
+        apply_declarations(el, prev, &AppliedDeclarations::new());
+        prev.clear();
     }
 }
 
@@ -341,6 +782,276 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalization() {
+        assert_eq!(
+            "background-color: red; -webkit-transform: none; --mainColor: blue;",
+            Style::from([
+                ("backgroundColor", "red"),
+                ("WebkitTransform", "none"),
+                ("--mainColor", "blue"),
+            ])
+            .to_string()
+        );
+
+        assert_eq!(
+            Style::from([("color", "blue")]),
+            Style::from([("Color", "red"), ("color", "blue")]),
+        );
+
+        assert_eq!(
+            Style::from([("background-color", "red"), ("padding", "0.5rem")]),
+            Style::from([("backgroundColor", "red")])
+                .with_defaults([("background-color", "blue"), ("Padding", "0.5rem")]),
+        );
+    }
+
+    #[test]
+    fn test_with_vendor_prefixes() {
+        assert_eq!(
+            "color: red; -webkit-user-select: none; -moz-user-select: none; user-select: none;",
+            Style::from([("color", "red"), ("userSelect", "none")])
+                .with_vendor_prefixes([("user-select", [VendorPrefix::Webkit, VendorPrefix::Moz])])
+                .to_string()
+        );
+
+        assert_eq!(
+            Style::from("user-select: none;"),
+            Style::from("user-select: none;")
+                .with_vendor_prefixes([("user-select", [VendorPrefix::Webkit])]),
+        );
+    }
+
+    #[test]
+    fn test_sanitization() {
+        assert_eq!(
+            "color: red\\; background: url(evil.png);",
+            Style::from([("color", "red; background: url(evil.png)")]).to_string()
+        );
+        assert_eq!(
+            "padding: 0.5rem;",
+            Style::from([
+                ("background", "url(javascript:alert(1))"),
+                ("color; background", "red"),
+                ("padding", "0.5rem"),
+            ])
+            .to_string()
+        );
+        assert_eq!(
+            "content: '\\3c /style\\3e ';",
+            Style::from([("content", "'</style>'")]).to_string()
+        );
+
+        assert_eq!(
+            Ok("color: red; content: \"a;b\";".to_string()),
+            Style::from([("color", "red"), ("content", "\"a;b\"")]).try_to_string()
+        );
+        assert_eq!(Ok(String::new()), Style::default().try_to_string());
+        assert_eq!(
+            Err(StyleError::InvalidCharacter {
+                property: "color".to_string(),
+                value: "red; background: blue".to_string(),
+                character: ';',
+            }),
+            Style::from([("color", "red; background: blue")]).validate()
+        );
+        assert_eq!(
+            Err(StyleError::InvalidPropertyName(
+                "color; background".to_string()
+            )),
+            Style::from([("color; background", "red")]).validate()
+        );
+        assert!(
+            Style::from("background: url(javascript:alert(1));")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_get_and_contains() {
+        let style = Style::from("color: red; backgroundColor: blue; color: green;");
+        assert_eq!(Some("green"), style.get("color"));
+        assert_eq!(Some("blue"), style.get("background-color"));
+        assert!(style.contains("Color"));
+        assert!(!style.contains("padding"));
+
+        let style = Style::from([("color", Some("red")), ("padding", None)]);
+        assert_eq!(Some("red"), style.get("color"));
+        assert_eq!(None, style.get("padding"));
+        assert!(!style.contains("padding"));
+
+        assert_eq!(None, Style::default().get("color"));
+    }
+
+    #[test]
+    fn test_iter() {
+        assert_eq!(
+            vec![("color", "red"), ("padding", "0.5rem")],
+            Style::from("color: red; padding: 0.5rem;")
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![("color", "red"), ("padding", "0.5rem")],
+            Style::from([
+                ("color", Some("red")),
+                ("margin", None),
+                ("padding", Some("0.5rem"))
+            ])
+            .iter()
+            .collect::<Vec<_>>()
+        );
+        assert_eq!(0, Style::default().iter().count());
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut style = Style::from("color: red; padding: 0.5rem;");
+        assert_eq!(Some("red".to_string()), style.insert("color", "blue"));
+        assert_eq!(None, style.insert("marginTop", "1rem"));
+        assert_eq!(
+            Style::from([
+                ("color", "blue"),
+                ("padding", "0.5rem"),
+                ("margin-top", "1rem")
+            ]),
+            style
+        );
+
+        assert_eq!(Some("0.5rem".to_string()), style.remove("padding"));
+        assert_eq!(None, style.remove("padding"));
+        assert_eq!("color: blue; margin-top: 1rem;", style.to_string());
+
+        let mut style = Style::default();
+        assert_eq!(None, style.remove("color"));
+        assert_eq!(Style::default(), style);
+        style.insert("color", "red");
+        assert_eq!(Style::from([("color", "red")]), style);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut style = Style::default();
+        style.extend([("color", "red"), ("padding", "0.5rem")]);
+        style.extend([("color".to_string(), "blue".to_string())]);
+        assert_eq!(
+            Style::from([("color", "blue"), ("padding", "0.5rem")]),
+            style
+        );
+
+        let mut style = Style::from("color: red;");
+        style.extend([("padding", "0.5rem")]);
+        assert_eq!(
+            Style::from([("color", "red"), ("padding", "0.5rem")]),
+            style
+        );
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut style = Style::from("position: fixed; color: red; top: 0;");
+        style.retain(|property, _| !matches!(property, "position" | "top"));
+        assert_eq!(Style::from([("color", "red")]), style);
+
+        let mut style = Style::from([("color", Some("red")), ("padding", None)]);
+        style.retain(|_, value| value != "red");
+        assert_eq!(Style::from([("padding", None::<String>)]), style);
+    }
+
+    #[test]
+    fn test_applied_declarations() {
+        assert_eq!(
+            vec![
+                ("color".to_string(), "red".to_string()),
+                ("background-color".to_string(), "blue".to_string()),
+            ],
+            applied_declarations(&Style::from(
+                "color: red; backgroundColor: blue; background: url(javascript:alert(1));"
+            ))
+        );
+        assert_eq!(
+            vec![("color".to_string(), "red".to_string())],
+            applied_declarations(&Style::from([("color", Some("red")), ("padding", None)]))
+        );
+        assert_eq!(
+            vec![
+                ("color".to_string(), "red".to_string()),
+                ("margin".to_string(), "0".to_string()),
+                ("color".to_string(), "blue".to_string()),
+            ],
+            applied_declarations(&Style::from("color: red; margin: 0; color: blue;"))
+        );
+        assert!(applied_declarations(&Style::default()).is_empty());
+    }
+
+    #[test]
+    fn test_declaration_updates() {
+        use DeclarationUpdate::{Remove, Set};
+
+        let prev = applied_declarations(&Style::from("margin: 0; margin-top: 1rem;"));
+        let next = applied_declarations(&Style::from("margin: 2rem; margin-top: 1rem;"));
+        assert_eq!(
+            vec![Set("margin", "2rem"), Set("margin-top", "1rem")],
+            declaration_updates(&prev, &next)
+        );
+
+        let prev = applied_declarations(&Style::from("padding-top: 1rem; padding: 0; color: red;"));
+        let next = applied_declarations(&Style::from("padding-top: 1rem; color: red;"));
+        assert_eq!(
+            vec![
+                Remove("padding"),
+                Set("padding-top", "1rem"),
+                Set("color", "red")
+            ],
+            declaration_updates(&prev, &next)
+        );
+
+        let prev = applied_declarations(&Style::from("color: red; color: blue;"));
+        let next = applied_declarations(&Style::from("color: blue; color: red;"));
+        assert_eq!(
+            vec![Set("color", "blue"), Set("color", "red")],
+            declaration_updates(&prev, &next)
+        );
+
+        assert!(declaration_updates(&next, &next).is_empty());
+    }
+
+    #[test]
+    fn test_split_priority() {
+        assert_eq!(("red", None), split_priority("red"));
+        assert_eq!(("red", Some("important")), split_priority("red !important"));
+        assert_eq!(("red", Some("important")), split_priority("red!IMPORTANT "));
+        assert_eq!(
+            ("red", Some("important")),
+            split_priority("red ! important")
+        );
+        assert_eq!(("red !imp", None), split_priority("red !imp"));
+        assert_eq!(("url(\"a!b\")", None), split_priority("url(\"a!b\")"));
+    }
+
+    #[test]
+    fn test_with_defaults_shorthands() {
+        assert_eq!(
+            "color: red; margin: 1rem;",
+            Style::from([("margin", "1rem")])
+                .with_defaults([("margin-top", "0"), ("color", "red")])
+                .to_string()
+        );
+        assert_eq!(
+            "margin: 0; margin-top: 1rem;",
+            Style::from([("marginTop", "1rem")])
+                .with_defaults([("margin-top", "2rem"), ("margin", "0")])
+                .to_string()
+        );
+        assert_eq!(
+            "border: 1px solid black; border-top-color: red;",
+            Style::from([("border-top-color", "red")])
+                .with_defaults([("border", "1px solid black")])
+                .to_string()
+        );
+    }
+
     #[test]
     fn test_into_attribute_value() {
         assert_eq!(
@@ -353,4 +1064,44 @@ mod tests {
             "color: red; background-color: blue;"
         );
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(Style::default()), Style::parse(""));
+        assert_eq!(
+            Ok(Style::from([("color", "red"), ("margin-top", "1rem")])),
+            Style::parse("color: red; marginTop: 1rem;")
+        );
+        assert_eq!(
+            Err(ParseError {
+                kind: crate::ParseErrorKind::MissingColon,
+                line: 1,
+                column: 13,
+            }),
+            Style::parse("color: red; padding")
+        );
+    }
+
+    #[test]
+    fn test_is_equivalent() {
+        assert!(Style::default().is_equivalent(&Style::from("")));
+        assert!(
+            Style::from("color:red;  border: 1px  solid rgb(0,0,0)").is_equivalent(&Style::from([
+                ("border", "1px solid rgb(0, 0, 0)"),
+                ("color", "red")
+            ]))
+        );
+        assert!(
+            Style::from("color: blue; color: red; marginTop: 0;")
+                .is_equivalent(&Style::from([("margin-top", "0"), ("color", "red")]))
+        );
+        assert!(
+            Style::from([("color", Some("red")), ("padding", None)])
+                .is_equivalent(&Style::from("color: red"))
+        );
+
+        assert!(!Style::from("color: red").is_equivalent(&Style::from("color: blue")));
+        assert!(!Style::from("color: red").is_equivalent(&Style::from("color: red; padding: 0")));
+        assert!(!Style::from("content: 'a b'").is_equivalent(&Style::from("content: 'a  b'")));
+    }
 }
@@ -0,0 +1,659 @@
+use std::collections::HashSet;
+
+use indexmap::IndexSet;
+
+/// Merges Tailwind CSS classes, removing classes overridden by later conflicting classes.
+///
+/// Classes conflict if they set the same utility group with the same variants (e.g. `hover:` or `md:`, in any order)
+/// and important modifier. Shorthand utilities also override earlier longhand utilities, so `px-2 p-4` resolves to
+/// `p-4`, while `p-4 px-2` is kept. Arbitrary values (`p-[3px]`) and arbitrary properties (`[mask-type:alpha]`) are
+/// supported. Unknown classes are always kept.
+pub fn tailwind_merge(class: &str) -> String {
+    merge_tailwind_tokens(class.split_whitespace().map(str::to_string).collect())
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn merge_tailwind_tokens(tokens: IndexSet<String>) -> IndexSet<String> {
+    let mut blocked = HashSet::new();
+    let mut merged = tokens
+        .into_iter()
+        .rev()
+        .filter(|token| {
+            let Some((modifiers, group)) = conflict_key(token) else {
+                return true;
+            };
+
+            if blocked.contains(&format!("{modifiers}{group}")) {
+                return false;
+            }
+
+            blocked.insert(format!("{modifiers}{group}"));
+            for conflicting_group in conflicting_groups(&group) {
+                blocked.insert(format!("{modifiers}{conflicting_group}"));
+            }
+            true
+        })
+        .collect::<Vec<_>>();
+    merged.reverse();
+    merged.into_iter().collect()
+}
+
+/// Returns the variants and important modifier, normalized into a prefix, and the utility group of a class.
+fn conflict_key(class: &str) -> Option<(String, String)> {
+    let mut variants = split_top_level(class, ':');
+    let mut utility = variants.pop()?;
+
+    let mut important = false;
+    if let Some(stripped) = utility.strip_prefix('!') {
+        utility = stripped;
+        important = true;
+    } else if let Some(stripped) = utility.strip_suffix('!') {
+        utility = stripped;
+        important = true;
+    }
+
+    let group = utility_group(utility)?;
+
+    // The order of variants does not matter, except around arbitrary variants.
+    for run in variants.split_mut(|variant| variant.starts_with('[')) {
+        run.sort_unstable();
+    }
+
+    let mut modifiers = variants.join(":");
+    modifiers.push(':');
+    if important {
+        modifiers.push('!');
+    }
+
+    Some((modifiers, group))
+}
+
+/// Splits on a separator outside of brackets and parentheses.
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (index, char) in value.char_indices() {
+        match char {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            _ if char == separator && depth == 0 => {
+                parts.push(&value[start..index]);
+                start = index + char.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+fn utility_group(utility: &str) -> Option<String> {
+    if let Some(property) = utility
+        .strip_prefix('[')
+        .and_then(|utility| utility.strip_suffix(']'))
+        .and_then(|utility| utility.split_once(':'))
+        .map(|(property, _)| property)
+    {
+        return Some(format!("[{property}]"));
+    }
+
+    let utility = utility.strip_prefix('-').unwrap_or(utility);
+    if let Some(group) = standalone_group(utility) {
+        return Some(group.to_string());
+    }
+
+    // Prefixes can contain dashes, so the longest known prefix is used.
+    let end = utility.find('[').unwrap_or(utility.len());
+    let mut candidates = utility[..end]
+        .match_indices('-')
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if end == utility.len() {
+        candidates.push(utility.len());
+    }
+
+    candidates.into_iter().rev().find_map(|index| {
+        let (prefix, value) = (&utility[..index], &utility[index..]);
+        prefix_group(prefix, value.strip_prefix('-').unwrap_or(value)).map(str::to_string)
+    })
+}
+
+fn standalone_group(utility: &str) -> Option<&'static str> {
+    Some(match utility {
+        "block" | "inline-block" | "inline" | "flex" | "inline-flex" | "table" | "inline-table"
+        | "table-caption" | "table-cell" | "table-column" | "table-column-group"
+        | "table-footer-group" | "table-header-group" | "table-row-group" | "table-row"
+        | "flow-root" | "grid" | "inline-grid" | "contents" | "list-item" | "hidden" => "display",
+        "static" | "fixed" | "absolute" | "relative" | "sticky" => "position",
+        "visible" | "invisible" | "collapse" => "visibility",
+        "isolate" | "isolation-auto" => "isolation",
+        "underline" | "overline" | "line-through" | "no-underline" => "text-decoration-line",
+        "uppercase" | "lowercase" | "capitalize" | "normal-case" => "text-transform",
+        "italic" | "not-italic" => "font-style",
+        "antialiased" | "subpixel-antialiased" => "font-smoothing",
+        "truncate" | "text-ellipsis" | "text-clip" => "text-overflow",
+        "sr-only" | "not-sr-only" => "sr",
+        _ => return None,
+    })
+}
+
+/// Prefixes of which the value does not change the utility group.
+const SIMPLE_PREFIXES: &[&str] = &[
+    "p",
+    "px",
+    "py",
+    "ps",
+    "pe",
+    "pt",
+    "pr",
+    "pb",
+    "pl",
+    "m",
+    "mx",
+    "my",
+    "ms",
+    "me",
+    "mt",
+    "mr",
+    "mb",
+    "ml",
+    "w",
+    "h",
+    "min-w",
+    "min-h",
+    "max-w",
+    "max-h",
+    "size",
+    "gap",
+    "gap-x",
+    "gap-y",
+    "inset",
+    "inset-x",
+    "inset-y",
+    "top",
+    "right",
+    "bottom",
+    "left",
+    "start",
+    "end",
+    "z",
+    "order",
+    "basis",
+    "opacity",
+    "leading",
+    "tracking",
+    "indent",
+    "items",
+    "justify",
+    "justify-items",
+    "justify-self",
+    "self",
+    "place-content",
+    "place-items",
+    "place-self",
+    "grid-cols",
+    "grid-rows",
+    "grid-flow",
+    "auto-cols",
+    "auto-rows",
+    "col-start",
+    "col-end",
+    "row-start",
+    "row-end",
+    "cursor",
+    "select",
+    "overflow",
+    "overflow-x",
+    "overflow-y",
+    "overscroll",
+    "space-x",
+    "space-y",
+    "translate-x",
+    "translate-y",
+    "rotate",
+    "scale",
+    "scale-x",
+    "scale-y",
+    "skew-x",
+    "skew-y",
+    "origin",
+    "duration",
+    "ease",
+    "delay",
+    "animate",
+    "aspect",
+    "whitespace",
+    "break-before",
+    "break-after",
+    "break-inside",
+    "break",
+    "fill",
+    "pointer-events",
+    "line-clamp",
+    "columns",
+    "align",
+    "from",
+    "via",
+    "to",
+    "underline-offset",
+    "outline-offset",
+    "float",
+    "clear",
+    "mix-blend",
+    "bg-blend",
+    "bg-clip",
+    "bg-origin",
+    "list-image",
+    "accent",
+    "caret",
+    "will-change",
+    "touch",
+    "appearance",
+];
+
+/// Prefixes which are valid without a value, e.g. `rounded` and `rounded-lg`.
+const OPTIONAL_VALUE_PREFIXES: &[&str] = &[
+    "rounded",
+    "rounded-s",
+    "rounded-e",
+    "rounded-t",
+    "rounded-r",
+    "rounded-b",
+    "rounded-l",
+    "rounded-ss",
+    "rounded-se",
+    "rounded-ee",
+    "rounded-es",
+    "rounded-tl",
+    "rounded-tr",
+    "rounded-br",
+    "rounded-bl",
+    "grow",
+    "shrink",
+    "resize",
+    "transition",
+    "blur",
+    "drop-shadow",
+    "grayscale",
+    "invert",
+    "sepia",
+    "divide-x",
+    "divide-y",
+];
+
+const FONT_SIZES: &[&str] = &[
+    "xs", "sm", "base", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl", "7xl", "8xl", "9xl",
+];
+
+const FONT_WEIGHTS: &[&str] = &[
+    "thin",
+    "extralight",
+    "light",
+    "normal",
+    "medium",
+    "semibold",
+    "bold",
+    "extrabold",
+    "black",
+];
+
+fn prefix_group(prefix: &str, value: &str) -> Option<&'static str> {
+    if let Some(group) = SIMPLE_PREFIXES.iter().find(|group| **group == prefix) {
+        return (!value.is_empty()).then_some(*group);
+    }
+    if let Some(group) = OPTIONAL_VALUE_PREFIXES
+        .iter()
+        .find(|group| **group == prefix)
+    {
+        return Some(*group);
+    }
+
+    if value.is_empty() {
+        return match prefix {
+            "border" => Some("border-w"),
+            "border-x" | "border-y" | "border-s" | "border-e" | "border-t" | "border-r"
+            | "border-b" | "border-l" => border_groups(prefix).map(|(width, _)| width),
+            "ring" => Some("ring-w"),
+            "outline" => Some("outline-style"),
+            "shadow" => Some("shadow"),
+            _ => None,
+        };
+    }
+
+    Some(match prefix {
+        "text" => {
+            let size = split_top_level(value, '/')[0];
+            if FONT_SIZES.contains(&size) || is_arbitrary_length(size) {
+                "font-size"
+            } else if matches!(
+                value,
+                "left" | "center" | "right" | "justify" | "start" | "end"
+            ) {
+                "text-align"
+            } else if matches!(value, "wrap" | "nowrap" | "balance" | "pretty") {
+                "text-wrap"
+            } else {
+                "text-color"
+            }
+        }
+        "font" => {
+            if FONT_WEIGHTS.contains(&value)
+                || is_number(value)
+                || arbitrary_label(value) == Some("number")
+            {
+                "font-weight"
+            } else {
+                "font-family"
+            }
+        }
+        "bg" => match value {
+            "fixed" | "local" | "scroll" => "bg-attachment",
+            "repeat" | "no-repeat" | "repeat-x" | "repeat-y" | "repeat-round" | "repeat-space" => {
+                "bg-repeat"
+            }
+            "auto" | "cover" | "contain" => "bg-size",
+            "bottom" | "center" | "left" | "left-bottom" | "left-top" | "right"
+            | "right-bottom" | "right-top" | "top" => "bg-position",
+            "none" => "bg-image",
+            _ if value.starts_with("gradient-")
+                || value.starts_with("linear-")
+                || value.starts_with("radial")
+                || value.starts_with("conic") =>
+            {
+                "bg-image"
+            }
+            _ => match arbitrary_label(value) {
+                Some("length" | "size") => "bg-size",
+                Some("position") => "bg-position",
+                Some("image" | "url") => "bg-image",
+                _ if value.starts_with("[url(") => "bg-image",
+                _ => "bg-color",
+            },
+        },
+        "border" => match value {
+            "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => "border-style",
+            "collapse" | "separate" => "border-collapse",
+            _ if is_length(value) => "border-w",
+            _ => "border-color",
+        },
+        "border-x" | "border-y" | "border-s" | "border-e" | "border-t" | "border-r"
+        | "border-b" | "border-l" => {
+            let (width, color) = border_groups(prefix)?;
+            if is_length(value) { width } else { color }
+        }
+        "ring" => match value {
+            "inset" => "ring-inset",
+            _ if is_length(value) => "ring-w",
+            _ => "ring-color",
+        },
+        "ring-offset" => {
+            if is_length(value) {
+                "ring-offset-w"
+            } else {
+                "ring-offset-color"
+            }
+        }
+        "outline" => match value {
+            "none" | "dashed" | "dotted" | "double" => "outline-style",
+            _ if is_length(value) => "outline-w",
+            _ => "outline-color",
+        },
+        "shadow" => match value {
+            "sm" | "md" | "lg" | "xl" | "2xl" | "inner" | "none" => "shadow",
+            _ if is_arbitrary(value) && arbitrary_label(value) != Some("color") => "shadow",
+            _ => "shadow-color",
+        },
+        "stroke" => {
+            if is_length(value) {
+                "stroke-w"
+            } else {
+                "stroke"
+            }
+        }
+        "decoration" => match value {
+            "solid" | "double" | "dotted" | "dashed" | "wavy" => "decoration-style",
+            "auto" | "from-font" => "decoration-thickness",
+            _ if is_length(value) => "decoration-thickness",
+            _ => "decoration-color",
+        },
+        "flex" => match value {
+            "row" | "row-reverse" | "col" | "col-reverse" => "flex-direction",
+            "wrap" | "wrap-reverse" | "nowrap" => "flex-wrap",
+            _ => "flex",
+        },
+        "object" => match value {
+            "contain" | "cover" | "fill" | "none" | "scale-down" => "object-fit",
+            _ => "object-position",
+        },
+        "list" => match value {
+            "inside" | "outside" => "list-position",
+            _ => "list-style-type",
+        },
+        "content" => {
+            if value == "none" || is_arbitrary(value) {
+                "content"
+            } else {
+                "align-content"
+            }
+        }
+        "col" if value == "auto" || value.starts_with("span-") || is_arbitrary(value) => "col",
+        "row" if value == "auto" || value.starts_with("span-") || is_arbitrary(value) => "row",
+        "table" if matches!(value, "auto" | "fixed") => "table-layout",
+        "box" if matches!(value, "border" | "content") => "box-sizing",
+        _ => return None,
+    })
+}
+
+fn border_groups(prefix: &str) -> Option<(&'static str, &'static str)> {
+    Some(match prefix {
+        "border-x" => ("border-w-x", "border-color-x"),
+        "border-y" => ("border-w-y", "border-color-y"),
+        "border-s" => ("border-w-s", "border-color-s"),
+        "border-e" => ("border-w-e", "border-color-e"),
+        "border-t" => ("border-w-t", "border-color-t"),
+        "border-r" => ("border-w-r", "border-color-r"),
+        "border-b" => ("border-w-b", "border-color-b"),
+        "border-l" => ("border-w-l", "border-color-l"),
+        _ => return None,
+    })
+}
+
+/// Groups overridden by a shorthand group.
+fn conflicting_groups(group: &str) -> &'static [&'static str] {
+    match group {
+        "p" => &["px", "py", "ps", "pe", "pt", "pr", "pb", "pl"],
+        "px" => &["pr", "pl"],
+        "py" => &["pt", "pb"],
+        "m" => &["mx", "my", "ms", "me", "mt", "mr", "mb", "ml"],
+        "mx" => &["mr", "ml"],
+        "my" => &["mt", "mb"],
+        "inset" => &[
+            "inset-x", "inset-y", "start", "end", "top", "right", "bottom", "left",
+        ],
+        "inset-x" => &["right", "left"],
+        "inset-y" => &["top", "bottom"],
+        "size" => &["w", "h"],
+        "gap" => &["gap-x", "gap-y"],
+        "overflow" => &["overflow-x", "overflow-y"],
+        "scale" => &["scale-x", "scale-y"],
+        "flex" => &["basis", "grow", "shrink"],
+        "font-size" => &["leading"],
+        "rounded" => &[
+            "rounded-s",
+            "rounded-e",
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-ss",
+            "rounded-se",
+            "rounded-ee",
+            "rounded-es",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+        ],
+        "rounded-s" => &["rounded-ss", "rounded-es"],
+        "rounded-e" => &["rounded-se", "rounded-ee"],
+        "rounded-t" => &["rounded-tl", "rounded-tr"],
+        "rounded-r" => &["rounded-tr", "rounded-br"],
+        "rounded-b" => &["rounded-br", "rounded-bl"],
+        "rounded-l" => &["rounded-tl", "rounded-bl"],
+        "border-w" => &[
+            "border-w-x",
+            "border-w-y",
+            "border-w-s",
+            "border-w-e",
+            "border-w-t",
+            "border-w-r",
+            "border-w-b",
+            "border-w-l",
+        ],
+        "border-w-x" => &["border-w-r", "border-w-l"],
+        "border-w-y" => &["border-w-t", "border-w-b"],
+        "border-color" => &[
+            "border-color-x",
+            "border-color-y",
+            "border-color-s",
+            "border-color-e",
+            "border-color-t",
+            "border-color-r",
+            "border-color-b",
+            "border-color-l",
+        ],
+        "border-color-x" => &["border-color-r", "border-color-l"],
+        "border-color-y" => &["border-color-t", "border-color-b"],
+        _ => &[],
+    }
+}
+
+fn is_arbitrary(value: &str) -> bool {
+    value.starts_with('[') && value.ends_with(']')
+}
+
+/// Returns the type label of an arbitrary value, e.g. `length` for `[length:var(--width)]`.
+fn arbitrary_label(value: &str) -> Option<&str> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?;
+    let (label, _) = inner.split_once(':')?;
+    label
+        .chars()
+        .all(|char| char.is_ascii_lowercase() || char == '-')
+        .then_some(label)
+}
+
+fn is_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok()
+}
+
+fn is_arbitrary_length(value: &str) -> bool {
+    let Some(inner) = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+    else {
+        return false;
+    };
+
+    match arbitrary_label(value) {
+        Some(label) => matches!(label, "length" | "size" | "percentage"),
+        None => {
+            inner.starts_with(|char: char| char.is_ascii_digit() || char == '.')
+                || ["calc(", "min(", "max(", "clamp("]
+                    .iter()
+                    .any(|function| inner.starts_with(function))
+        }
+    }
+}
+
+fn is_length(value: &str) -> bool {
+    is_number(value) || value == "px" || is_arbitrary_length(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tailwind_merge() {
+        assert_eq!("p-4", tailwind_merge("p-2 p-4"));
+        assert_eq!("p-4", tailwind_merge("px-2 py-1 p-4"));
+        assert_eq!("p-4 px-2", tailwind_merge("p-4 px-2"));
+        assert_eq!("m-2 -mt-1", tailwind_merge("m-2 mt-4 -mt-1"));
+        assert_eq!("block", tailwind_merge("flex hidden block"));
+        assert_eq!("custom p-4 other", tailwind_merge("custom p-2 p-4 other"));
+    }
+
+    #[test]
+    fn test_tailwind_merge_values() {
+        assert_eq!(
+            "text-red-500 text-lg",
+            tailwind_merge("text-sm text-red-500 text-lg")
+        );
+        assert_eq!(
+            "text-blue-500/50",
+            tailwind_merge("text-red-500 text-blue-500/50")
+        );
+        assert_eq!(
+            "font-bold font-mono",
+            tailwind_merge("font-thin font-bold font-mono")
+        );
+        assert_eq!(
+            "border-dashed border-2 border-red-500",
+            tailwind_merge("border border-dashed border-2 border-red-500")
+        );
+        assert_eq!(
+            "border-t-2 border-t-red-500",
+            tailwind_merge("border-t-2 border-t-red-500")
+        );
+        assert_eq!(
+            "bg-cover bg-white",
+            tailwind_merge("bg-cover bg-black bg-white")
+        );
+        assert_eq!(
+            "flex-1 flex-col",
+            tailwind_merge("flex-row flex-1 flex-col")
+        );
+        assert_eq!(
+            "rounded-lg",
+            tailwind_merge("rounded-t-md rounded rounded-lg")
+        );
+        assert_eq!("text-lg/7", tailwind_merge("leading-3 text-lg/7"));
+    }
+
+    #[test]
+    fn test_tailwind_merge_variants() {
+        assert_eq!(
+            "p-2 hover:p-4 md:p-6",
+            tailwind_merge("p-2 hover:p-3 md:p-5 hover:p-4 md:p-6")
+        );
+        assert_eq!("md:hover:p-4", tailwind_merge("hover:md:p-2 md:hover:p-4"));
+        assert_eq!("p-2 p-4!", tailwind_merge("p-2 !p-3 p-4!"));
+    }
+
+    #[test]
+    fn test_tailwind_merge_arbitrary() {
+        assert_eq!("p-[3px]", tailwind_merge("p-2 p-[3px]"));
+        assert_eq!(
+            "w-[calc(100%-2rem)]",
+            tailwind_merge("w-full w-[calc(100%-2rem)]")
+        );
+        assert_eq!("text-[14px]", tailwind_merge("text-sm text-[14px]"));
+        assert_eq!(
+            "text-sm text-[#fff]",
+            tailwind_merge("text-sm text-red-500 text-[#fff]")
+        );
+        assert_eq!(
+            "[mask-type:alpha]",
+            tailwind_merge("[mask-type:luminance] [mask-type:alpha]")
+        );
+        assert_eq!(
+            "bg-[url(/a.png)] bg-red-500",
+            tailwind_merge("bg-[url(/a.png)] bg-red-500")
+        );
+    }
+}
@@ -0,0 +1,731 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use indexmap::IndexMap;
+use leptos::prelude::{
+    Effect, GetUntracked, Owner, RwSignal, Set, Signal, is_server, request_animation_frame,
+};
+
+use crate::{
+    parse::normalize_value, property::normalize_property_name, reactive::ReactiveStyle,
+    style::Style,
+};
+
+/// Easing function of a transition, matching the CSS `<easing-function>` keywords.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    Linear,
+    #[default]
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Cubic Bézier curve with control points `(x1, y1)` and `(x2, y2)`, like `cubic-bezier(x1, y1, x2, y2)`.
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    /// Maps the progress of a transition, between 0 and 1, to the eased progress.
+    pub fn apply(&self, progress: f64) -> f64 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        let (x1, y1, x2, y2) = match *self {
+            Self::Linear => return progress,
+            Self::Ease => (0.25, 0.1, 0.25, 1.0),
+            Self::EaseIn => (0.42, 0.0, 1.0, 1.0),
+            Self::EaseOut => (0.0, 0.0, 0.58, 1.0),
+            Self::EaseInOut => (0.42, 0.0, 0.58, 1.0),
+            Self::CubicBezier(x1, y1, x2, y2) => (x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2),
+        };
+
+        if progress == 0.0 || progress == 1.0 {
+            return progress;
+        }
+
+        let bezier = |t: f64, p1: f64, p2: f64| {
+            3.0 * (1.0 - t).powi(2) * t * p1 + 3.0 * (1.0 - t) * t.powi(2) * p2 + t.powi(3)
+        };
+
+        // Find the curve parameter for the progress on the x axis by bisection, as x is monotonic.
+        let (mut low, mut high) = (0.0, 1.0);
+        let mut t = progress;
+        for _ in 0..64 {
+            let x = bezier(t, x1, x2);
+            if (x - progress).abs() < 1e-7 {
+                break;
+            }
+            if x < progress {
+                low = t;
+            } else {
+                high = t;
+            }
+            t = (low + high) / 2.0;
+        }
+
+        bezier(t, y1, y2)
+    }
+}
+
+/// Options of a style transition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransitionOptions {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Default for TransitionOptions {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(150),
+            easing: Easing::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ValueToken {
+    Number(f64, String),
+    Color([f64; 4]),
+    Text(String),
+}
+
+fn tokenize(value: &str) -> Vec<ValueToken> {
+    let mut tokens = vec![];
+    let mut rest = value;
+
+    while let Some(char) = rest.chars().next() {
+        let is_word_start = !value[..value.len() - rest.len()].ends_with(is_identifier_char);
+
+        if let Some(length) = verbatim_length(rest, is_word_start) {
+            match tokens.last_mut() {
+                Some(ValueToken::Text(text)) => text.push_str(&rest[..length]),
+                _ => tokens.push(ValueToken::Text(rest[..length].to_string())),
+            }
+            rest = &rest[length..];
+        } else if let Some((color, remaining)) = is_word_start
+            .then(|| parse_named_color(rest))
+            .flatten()
+            .or_else(|| parse_color(rest))
+        {
+            tokens.push(ValueToken::Color(color));
+            rest = remaining;
+        } else if let Some((number, unit, remaining)) = parse_number(rest) {
+            tokens.push(ValueToken::Number(number, unit.to_string()));
+            rest = remaining;
+        } else {
+            match tokens.last_mut() {
+                Some(ValueToken::Text(text)) => text.push(char),
+                _ => tokens.push(ValueToken::Text(char.to_string())),
+            }
+            rest = &rest[char.len_utf8()..];
+        }
+    }
+
+    tokens
+}
+
+/// Returns the length of a string or URL at the start of the value, which are not interpolated.
+fn verbatim_length(value: &str, is_word_start: bool) -> Option<usize> {
+    let (start, end) = if value.starts_with(['"', '\'']) {
+        (1, &value[..1])
+    } else if is_word_start && value.len() >= 4 && value[..4].eq_ignore_ascii_case("url(") {
+        (4, ")")
+    } else {
+        return None;
+    };
+
+    Some(
+        value[start..]
+            .find(end)
+            .map_or(value.len(), |index| start + index + end.len()),
+    )
+}
+
+fn is_identifier_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '-' || char == '_'
+}
+
+fn parse_number(value: &str) -> Option<(f64, &str, &str)> {
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let digits = unsigned
+        .find(|char: char| !char.is_ascii_digit() && char != '.')
+        .unwrap_or(unsigned.len());
+    if digits == 0 || !unsigned[..digits].contains(|char: char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    let number_end = value.len() - unsigned.len() + digits;
+    let number = value[..number_end].parse().ok()?;
+    let unit_end = value[number_end..]
+        .find(|char: char| !char.is_ascii_alphabetic() && char != '%')
+        .map_or(value.len(), |index| number_end + index);
+
+    Some((number, &value[number_end..unit_end], &value[unit_end..]))
+}
+
+/// Named colors of CSS Color Module Level 4, sorted by name.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+fn parse_named_color(value: &str) -> Option<([f64; 4], &str)> {
+    let end = value
+        .find(|char: char| !is_identifier_char(char))
+        .unwrap_or(value.len());
+    let name = value[..end].to_ascii_lowercase();
+    let index = NAMED_COLORS
+        .binary_search_by(|(color, _)| color.cmp(&name.as_str()))
+        .ok()?;
+
+    let rgb = NAMED_COLORS[index].1;
+    let channel = |shift: u32| f64::from((rgb >> shift) & 0xff);
+    Some(([channel(16), channel(8), channel(0), 1.0], &value[end..]))
+}
+
+fn parse_color(value: &str) -> Option<([f64; 4], &str)> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let end = hex
+            .find(|char: char| !char.is_ascii_hexdigit())
+            .unwrap_or(hex.len());
+        if hex[end..].starts_with(is_identifier_char) {
+            return None;
+        }
+
+        let channel = |index: usize, length: usize| {
+            let digits = &hex[index * length..(index + 1) * length];
+            let digits = if length == 1 {
+                digits.repeat(2)
+            } else {
+                digits.to_string()
+            };
+            u8::from_str_radix(&digits, 16).ok().map(f64::from)
+        };
+        let length = match end {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return None,
+        };
+        let alpha = if end == 4 || end == 8 {
+            channel(3, length)? / 255.0
+        } else {
+            1.0
+        };
+
+        return Some((
+            [
+                channel(0, length)?,
+                channel(1, length)?,
+                channel(2, length)?,
+                alpha,
+            ],
+            &hex[end..],
+        ));
+    }
+
+    if let Some(rest) = value.strip_prefix("transparent")
+        && !rest.starts_with(is_identifier_char)
+    {
+        return Some(([0.0, 0.0, 0.0, 0.0], rest));
+    }
+
+    let rest = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))?;
+    let end = rest.find(')')?;
+    let channels = rest[..end]
+        .split([',', ' ', '/'])
+        .filter(|channel| !channel.is_empty())
+        .enumerate()
+        .map(|(index, channel)| match channel.strip_suffix('%') {
+            // Percentages of the color channels are relative to 255, and of the alpha channel to 1.
+            Some(percentage) => percentage.parse::<f64>().ok().map(|value| {
+                if index < 3 {
+                    value * 2.55
+                } else {
+                    value / 100.0
+                }
+            }),
+            None => channel.parse::<f64>().ok(),
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match channels[..] {
+        [r, g, b] => Some(([r, g, b, 1.0], &rest[end + 1..])),
+        [r, g, b, a] => Some(([r, g, b, a], &rest[end + 1..])),
+        _ => None,
+    }
+}
+
+fn format_number(value: f64) -> String {
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    if formatted == "-0" {
+        "0".to_string()
+    } else {
+        formatted.to_string()
+    }
+}
+
+fn interpolate_color(from: [f64; 4], to: [f64; 4], progress: f64) -> String {
+    // Colors are interpolated with premultiplied alpha, so transitions from `transparent` do not darken.
+    let alpha = (from[3] + (to[3] - from[3]) * progress).clamp(0.0, 1.0);
+    let channel = |index: usize| {
+        if alpha == 0.0 {
+            return 0.0;
+        }
+        let from = from[index] * from[3];
+        let to = to[index] * to[3];
+        ((from + (to - from) * progress) / alpha)
+            .clamp(0.0, 255.0)
+            .round()
+    };
+
+    if alpha == 1.0 {
+        format!("rgb({}, {}, {})", channel(0), channel(1), channel(2))
+    } else {
+        format!(
+            "rgba({}, {}, {}, {})",
+            channel(0),
+            channel(1),
+            channel(2),
+            format_number(alpha)
+        )
+    }
+}
+
+/// Interpolates numbers with units and colors of two values with the same structure.
+fn interpolate_value(from: &str, to: &str, progress: f64) -> Option<String> {
+    let from = tokenize(&normalize_value(from));
+    let to = tokenize(&normalize_value(to));
+    if from.len() != to.len() {
+        return None;
+    }
+
+    from.into_iter()
+        .zip(to)
+        .map(|(from, to)| match (from, to) {
+            (ValueToken::Number(from, from_unit), ValueToken::Number(to, to_unit)) => {
+                let unit = if from_unit == to_unit || (from == 0.0 && from_unit.is_empty()) {
+                    to_unit
+                } else if to == 0.0 && to_unit.is_empty() {
+                    from_unit
+                } else {
+                    return None;
+                };
+                Some(format!(
+                    "{}{unit}",
+                    format_number(from + (to - from) * progress)
+                ))
+            }
+            (ValueToken::Color(from), ValueToken::Color(to)) => {
+                Some(interpolate_color(from, to, progress))
+            }
+            (ValueToken::Text(from), ValueToken::Text(to)) => (from == to).then_some(from),
+            _ => None,
+        })
+        .collect()
+}
+
+fn declaration_map(style: &Style) -> IndexMap<String, String> {
+    style
+        .iter()
+        .map(|(property, value)| (normalize_property_name(property), value.to_string()))
+        .collect()
+}
+
+/// Interpolates between two styles.
+///
+/// Numbers with the same unit (or unitless zero) and colors, including named colors, are interpolated, if both values have the same structure,
+/// e.g. `translate(0, 0)` and `translate(10px, 2rem)`. Other values, and properties only set in one of the styles,
+/// switch halfway, like discrete CSS animations.
+pub fn interpolate_styles(from: &Style, to: &Style, progress: f64) -> Style {
+    let from = declaration_map(from);
+    let to = declaration_map(to);
+    let discrete = progress >= 0.5;
+
+    let mut declarations = IndexMap::new();
+    for (property, to_value) in &to {
+        let value = match from.get(property) {
+            Some(from_value) => {
+                interpolate_value(from_value, to_value, progress).unwrap_or_else(|| {
+                    if discrete {
+                        to_value.clone()
+                    } else {
+                        from_value.clone()
+                    }
+                })
+            }
+            None if discrete => to_value.clone(),
+            None => continue,
+        };
+        declarations.insert(property.clone(), value);
+    }
+    if !discrete {
+        for (property, from_value) in &from {
+            if !to.contains_key(property) {
+                declarations.insert(property.clone(), from_value.clone());
+            }
+        }
+    }
+
+    if declarations.is_empty() {
+        Style::default()
+    } else {
+        Style::from(declarations)
+    }
+}
+
+/// Transitions a style, returning a reactive style which is updated on every animation frame.
+///
+/// On the client, the style animates from `from` to `to` when created, and from its current state to `to` whenever
+/// `to` changes. During server-side rendering and hydration the end state `to` is rendered, so hydrated elements do not
+/// animate in.
+pub fn use_style_transition<F: Into<Style>, T: Into<ReactiveStyle>>(
+    from: F,
+    to: T,
+    options: TransitionOptions,
+) -> ReactiveStyle {
+    let to = to.into();
+    let hydrating = Owner::current_shared_context()
+        .is_some_and(|shared_context| shared_context.during_hydration());
+    let initial = if cfg!(feature = "ssr") || is_server() || hydrating {
+        to.get_untracked()
+    } else {
+        from.into()
+    };
+
+    let current = RwSignal::new(initial);
+    let generation = Arc::new(AtomicUsize::new(0));
+
+    Effect::new(move |_| {
+        let target = to.get();
+        let id = generation.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let start = current.get_untracked();
+        if !start.is_equivalent(&target) {
+            animate(AnimationFrame {
+                from: start,
+                to: target,
+                options,
+                current,
+                generation: generation.clone(),
+                id,
+                start_time: now(),
+            });
+        }
+    });
+
+    ReactiveStyle::from(Signal::<Style>::from(current))
+}
+
+fn now() -> f64 {
+    leptos::web_sys::js_sys::Date::now()
+}
+
+struct AnimationFrame {
+    from: Style,
+    to: Style,
+    options: TransitionOptions,
+    current: RwSignal<Style>,
+    generation: Arc<AtomicUsize>,
+    id: usize,
+    start_time: f64,
+}
+
+fn animate(frame: AnimationFrame) {
+    request_animation_frame(move || {
+        // A newer transition started.
+        if frame.generation.load(Ordering::Relaxed) != frame.id {
+            return;
+        }
+
+        let duration = frame.options.duration.as_secs_f64() * 1000.0;
+        let progress = if duration > 0.0 {
+            ((now() - frame.start_time) / duration).min(1.0)
+        } else {
+            1.0
+        };
+
+        let style = if progress < 1.0 {
+            interpolate_styles(&frame.from, &frame.to, frame.options.easing.apply(progress))
+        } else {
+            frame.to.clone()
+        };
+
+        // The signal is disposed when its owner is cleaned up.
+        if frame.current.try_set(style).is_none() && progress < 1.0 {
+            animate(frame);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing() {
+        for easing in [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(0.0, easing.apply(0.0));
+            assert_eq!(1.0, easing.apply(1.0));
+            assert_eq!(1.0, easing.apply(2.0));
+        }
+
+        assert_eq!(0.25, Easing::Linear.apply(0.25));
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!(Easing::EaseIn.apply(0.25) < 0.25);
+        assert!(Easing::EaseOut.apply(0.25) > 0.25);
+        assert!((Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.3) - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interpolate_value() {
+        assert_eq!(
+            Some("50px".to_string()),
+            interpolate_value("0", "100px", 0.5)
+        );
+        assert_eq!(Some("0.25".to_string()), interpolate_value("0", "1", 0.25));
+        assert_eq!(
+            Some("translate(5px,-1rem)".to_string()),
+            interpolate_value("translate(0px, 0rem)", "translate(10px, -2rem)", 0.5)
+        );
+        assert_eq!(
+            Some("rgb(128, 0, 128)".to_string()),
+            interpolate_value("#f00", "rgb(0, 0, 255)", 0.5)
+        );
+        assert_eq!(
+            Some("rgba(255, 0, 0, 0.5)".to_string()),
+            interpolate_value("transparent", "#ff0000", 0.5)
+        );
+        assert_eq!(
+            Some("rgb(255, 0, 0)".to_string()),
+            interpolate_value("rgb(100%, 0%, 0%)", "rgb(255, 0, 0)", 0.5)
+        );
+        assert_eq!(
+            Some("rgba(85, 0, 170, 0.75)".to_string()),
+            interpolate_value("rgb(100% 0% 0% / 50%)", "rgb(0, 0, 255)", 0.5)
+        );
+        assert_eq!(
+            Some("1px solid rgb(128, 128, 128)".to_string()),
+            interpolate_value("1px solid #000", "1px solid #fff", 0.5)
+        );
+
+        assert_eq!(
+            Some("rgb(128, 0, 128)".to_string()),
+            interpolate_value("red", "Blue", 0.5)
+        );
+        assert_eq!(
+            Some("1px solid rgba(0, 128, 0, 0.5)".to_string()),
+            interpolate_value("1px solid transparent", "1px solid green", 0.5)
+        );
+        // Named colors are only resolved as whole words.
+        assert_eq!(
+            Some("bored 5px".to_string()),
+            interpolate_value("bored 0px", "bored 10px", 0.5)
+        );
+        assert_eq!(
+            Some("url(red-1.png) 5px".to_string()),
+            interpolate_value("url(red-1.png) 0px", "url(red-1.png) 10px", 0.5)
+        );
+        assert_eq!(
+            Some("\"red\" 5px".to_string()),
+            interpolate_value("\"red\" 0px", "\"red\" 10px", 0.5)
+        );
+        assert_eq!(
+            Some("0 0 2px rgb(139, 0, 0)".to_string()),
+            interpolate_value("0 0 4px darkred", "0 0 0 darkred", 0.5)
+        );
+
+        assert_eq!(None, interpolate_value("10px", "50%", 0.5));
+        assert_eq!(None, interpolate_value("block", "none", 0.5));
+        assert_eq!(None, interpolate_value("1px solid", "1px", 0.5));
+    }
+
+    #[test]
+    fn test_interpolate_styles() {
+        let from = Style::from([("opacity", "0"), ("height", "0"), ("display", "none")]);
+        let to = Style::from("opacity: 1; height: 200px; display: block; color: red;");
+
+        assert!(
+            interpolate_styles(&from, &to, 0.25).is_equivalent(&Style::from([
+                ("opacity", "0.25"),
+                ("height", "50px"),
+                ("display", "none"),
+            ]))
+        );
+        assert!(
+            interpolate_styles(&from, &to, 0.75).is_equivalent(&Style::from([
+                ("opacity", "0.75"),
+                ("height", "150px"),
+                ("display", "block"),
+                ("color", "red"),
+            ]))
+        );
+        assert!(interpolate_styles(&from, &to, 1.0).is_equivalent(&to));
+        assert!(
+            interpolate_styles(&from, &to, 0.0).is_equivalent(&Style::from([
+                ("opacity", "0"),
+                ("height", "0px"),
+                ("display", "none"),
+            ]))
+        );
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "ssr")]
+
+use leptos::prelude::*;
+use leptos_style::Class;
+
+#[component]
+fn Button(#[prop(into, optional)] class: Class, active: Signal<bool>) -> impl IntoView {
+    view! {
+        <button class=class.with_defaults(Class::from([("button", true)])).with_defaults([("active", active)])>
+            "Button"
+        </button>
+    }
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        view! {
+            <Button class="primary button" active=Signal::stored(true) />
+        }
+        .to_html()
+    });
+
+    assert_eq!(
+        "<button class=\"active button primary\">Button</button>",
+        html
+    );
+}
@@ -36,3 +36,17 @@ fn App() -> impl IntoView {
         </Button>
     }
 }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn test_style_directive() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        view! {
+            <div style=Style::from("color: red") style:opacity="0.5">"Content"</div>
+        }
+        .to_html()
+    });
+
+    assert_eq!("<div style=\"color: red;opacity:0.5;\">Content</div>", html);
+}
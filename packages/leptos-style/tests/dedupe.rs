@@ -0,0 +1,120 @@
+#![cfg(feature = "ssr")]
+
+use leptos::prelude::*;
+use leptos_style::{ScopedStyleSheet, Style, StyleDedupeOptions, provide_style_dedupe};
+
+#[component]
+fn Item(label: &'static str) -> impl IntoView {
+    let style = Style::from([
+        ("display", "flex"),
+        ("padding", "0.5rem 1rem"),
+        ("border-bottom", "1px solid gray"),
+    ]);
+
+    view! {
+        <li style=style>{label}</li>
+    }
+}
+
+#[component]
+fn App() -> impl IntoView {
+    view! {
+        <ul>
+            <Item label="First" />
+            <Item label="Second" />
+            <Item label="Third" />
+        </ul>
+        <ScopedStyleSheet />
+    }
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        provide_style_dedupe(StyleDedupeOptions::default());
+        view! { <App /> }.to_html()
+    });
+
+    let declarations = "display: flex; padding: 0.5rem 1rem; border-bottom: 1px solid gray;";
+    // Inline in the first item and in the hoisted rule.
+    assert_eq!(2, html.matches(declarations).count());
+    assert_eq!(
+        1,
+        html.matches(&format!("<li style=\"{declarations}\">"))
+            .count()
+    );
+
+    let start = html.find("--ls-hoisted-").expect("style should be hoisted");
+    let marker = &html[start..start + "--ls-hoisted-".len() + 16];
+    assert_eq!(
+        2,
+        html.matches(&format!("<li style=\"{marker}: 1;\">"))
+            .count()
+    );
+    assert!(html.contains(&format!(
+        "[style*=\"{marker}:\"] {{ {declarations} }}</style>"
+    )));
+}
+
+#[test]
+fn test_server_side_rendering_without_dedupe() {
+    let owner = Owner::new();
+    let html = owner.with(|| view! { <App /> }.to_html());
+
+    assert!(!html.contains("--ls-hoisted-"));
+    assert_eq!(
+        3,
+        html.matches("display: flex; padding: 0.5rem 1rem; border-bottom: 1px solid gray;")
+            .count()
+    );
+}
+
+#[component]
+fn LateItem(label: &'static str) -> impl IntoView {
+    let style = Style::from([
+        ("display", "grid"),
+        ("padding", "1rem 2rem"),
+        ("border-bottom", "1px solid black"),
+    ]);
+
+    view! {
+        <li style=style>{label}</li>
+    }
+}
+
+#[test]
+fn test_server_side_rendering_after_style_sheet() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        provide_style_dedupe(StyleDedupeOptions::default());
+        view! {
+            <ul>
+                <Item label="First" />
+                <Item label="Second" />
+            </ul>
+            <ScopedStyleSheet />
+            <ul>
+                <Item label="Third" />
+                <LateItem label="Fourth" />
+                <LateItem label="Fifth" />
+            </ul>
+        }
+        .to_html()
+    });
+
+    // The rule of the early style is in the style sheet, so it is still hoisted after the style sheet.
+    let start = html.find("--ls-hoisted-").expect("style should be hoisted");
+    let marker = &html[start..start + "--ls-hoisted-".len() + 16];
+    assert_eq!(
+        2,
+        html.matches(&format!("<li style=\"{marker}: 1;\">"))
+            .count()
+    );
+
+    // The late style would be hoisted into a rule which is not rendered, so it stays inline.
+    let late = "display: grid; padding: 1rem 2rem; border-bottom: 1px solid black;";
+    assert_eq!(2, html.matches(&format!("<li style=\"{late}\">")).count());
+    // Two markers and the rule of the early style.
+    assert_eq!(3, html.matches("--ls-hoisted-").count());
+}
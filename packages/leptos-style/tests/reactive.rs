@@ -0,0 +1,29 @@
+#![cfg(feature = "ssr")]
+
+use leptos::prelude::*;
+use leptos_style::ReactiveStyle;
+
+#[component]
+fn Button(#[prop(into, optional)] style: ReactiveStyle) -> impl IntoView {
+    view! {
+        <button style=style.with_defaults([("padding", "0.5rem")])>"Button"</button>
+    }
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        let color = RwSignal::new("red");
+
+        view! {
+            <Button style=move || format!("color: {};", color.get()) />
+        }
+        .to_html()
+    });
+
+    assert_eq!(
+        "<button style=\"padding: 0.5rem; color: red;\">Button</button>",
+        html
+    );
+}
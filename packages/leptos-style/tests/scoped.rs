@@ -0,0 +1,68 @@
+#![cfg(feature = "ssr")]
+
+use leptos::prelude::*;
+use leptos_style::{ScopedStyle, ScopedStyleSheet, provide_style_registry, use_scoped_style};
+
+#[component]
+fn Button(children: Children) -> impl IntoView {
+    let class = use_scoped_style(
+        ScopedStyle::new([("padding", "0.5rem")]).rule("&:hover", [("color", "red")]),
+    );
+
+    view! {
+        <button class=class>{children()}</button>
+    }
+}
+
+#[component]
+fn App() -> impl IntoView {
+    provide_style_registry();
+
+    view! {
+        <Button>"First"</Button>
+        <Button>"Second"</Button>
+        <ScopedStyleSheet />
+    }
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    let html = owner.with(|| view! { <App /> }.to_html());
+
+    let style = ScopedStyle::new([("padding", "0.5rem")]).rule("&:hover", [("color", "red")]);
+    let class_name = style.class_name();
+
+    assert_eq!(
+        2,
+        html.matches(&format!("<button class=\"{class_name}\">"))
+            .count()
+    );
+    assert_eq!(
+        1,
+        html.matches(&format!(".{class_name} {{ padding: 0.5rem; }}"))
+            .count()
+    );
+    assert!(html.contains(&format!(
+        "<style id=\"leptos-style-scoped\">{}</style>",
+        style.to_css()
+    )));
+}
+
+#[test]
+fn test_server_side_rendering_sanitized() {
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        provide_style_registry();
+        let class = use_scoped_style("color: red</style><script>alert(1)</script>");
+
+        view! {
+            <p class=class>"Text"</p>
+            <ScopedStyleSheet />
+        }
+        .to_html()
+    });
+
+    assert!(!html.contains("<script>"));
+    assert_eq!(1, html.matches("</style>").count());
+}
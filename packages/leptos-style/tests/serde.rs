@@ -0,0 +1,95 @@
+#![cfg(feature = "serde")]
+
+use leptos_style::{InnerStyle, Style};
+
+#[test]
+fn test_serialize() {
+    assert_eq!("null", serde_json::to_string(&Style::default()).unwrap());
+
+    assert_eq!(
+        r#""margin: 1rem; padding: 0.5rem;""#,
+        serde_json::to_string(&Style::from("margin: 1rem; padding: 0.5rem;")).unwrap()
+    );
+
+    assert_eq!(
+        r#"{"padding":"0.5rem","color":null,"background-color":"gray"}"#,
+        serde_json::to_string(&Style::from([
+            ("padding", Some("0.5rem")),
+            ("color", None),
+            ("backgroundColor", Some("gray")),
+        ]))
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_deserialize() {
+    assert_eq!(
+        Style::default(),
+        serde_json::from_str::<Style>("null").unwrap()
+    );
+
+    assert_eq!(
+        Style::from("color: red;"),
+        serde_json::from_str::<Style>(r#""color: red;""#).unwrap()
+    );
+
+    assert_eq!(
+        Style::from([
+            ("z-index", Some("1")),
+            ("color", None),
+            ("background-color", Some("gray")),
+        ]),
+        serde_json::from_str::<Style>(r#"{"z-index":"1","color":null,"backgroundColor":"gray"}"#)
+            .unwrap()
+    );
+
+    assert!(serde_json::from_str::<Style>("1").is_err());
+    assert!(serde_json::from_str::<Style>(r#"{"color":1}"#).is_err());
+}
+
+#[test]
+fn test_round_trip() {
+    for style in [
+        Style::default(),
+        Style::from("margin: 1rem; padding: 0.5rem;"),
+        Style::from([
+            ("padding", Some("0.5rem")),
+            ("color", None),
+            ("margin", Some("1rem")),
+        ]),
+    ] {
+        let json = serde_json::to_string(&style).unwrap();
+        assert_eq!(style, serde_json::from_str::<Style>(&json).unwrap());
+    }
+
+    let inner_style = InnerStyle::structured([("color", Some("red".to_string()))]);
+    let json = serde_json::to_string(&inner_style).unwrap();
+    assert_eq!(
+        inner_style,
+        serde_json::from_str::<InnerStyle>(&json).unwrap()
+    );
+}
+
+#[test]
+fn test_round_trip_non_self_describing() {
+    for style in [
+        Style::default(),
+        Style::from("margin: 1rem; padding: 0.5rem;"),
+        Style::from([
+            ("padding", Some("0.5rem")),
+            ("color", None),
+            ("margin", Some("1rem")),
+        ]),
+    ] {
+        let bytes = postcard::to_stdvec(&style).unwrap();
+        assert_eq!(style, postcard::from_bytes::<Style>(&bytes).unwrap());
+    }
+
+    let inner_style = InnerStyle::structured([("color", Some("red".to_string()))]);
+    let bytes = postcard::to_stdvec(&inner_style).unwrap();
+    assert_eq!(
+        inner_style,
+        postcard::from_bytes::<InnerStyle>(&bytes).unwrap()
+    );
+}
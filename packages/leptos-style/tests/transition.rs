@@ -0,0 +1,34 @@
+#![cfg(feature = "ssr")]
+
+use leptos::prelude::*;
+use leptos_style::{TransitionOptions, use_style_transition};
+
+#[component]
+fn Collapsible(open: Signal<bool>) -> impl IntoView {
+    let style = use_style_transition(
+        [("height", "0"), ("opacity", "0")],
+        move || {
+            if open.get() {
+                [("height", "200px"), ("opacity", "1")]
+            } else {
+                [("height", "0"), ("opacity", "0")]
+            }
+        },
+        TransitionOptions::default(),
+    );
+
+    view! {
+        <div style=style>"Content"</div>
+    }
+}
+
+#[test]
+fn test_server_side_rendering() {
+    let owner = Owner::new();
+    let html = owner.with(|| view! { <Collapsible open=Signal::stored(true) /> }.to_html());
+
+    assert_eq!(
+        "<div style=\"height: 200px; opacity: 1;\">Content</div>",
+        html
+    );
+}